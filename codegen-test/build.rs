@@ -1,19 +1,89 @@
-use schema2code::deserializer::SchemaDef;
-use std::{collections::HashMap, env, path::Path, process::Command};
+use schema2code::{GeneratorConfig, StringType};
+use std::{env, path::Path, process::Command};
 
-fn main() {
+fn generate(yaml_data: &str, file_name: &str, config: &GeneratorConfig) {
+    let codegen = schema2code::generate_rust_with(yaml_data, config).unwrap();
+    write_formatted(file_name, codegen);
+}
+
+fn write_formatted(file_name: &str, codegen: String) {
     let out_dir = env::var("OUT_DIR").unwrap();
-    let dest_path = Path::new(&out_dir).join("asyncapi.rs");
-    let yaml_data = include_str!("./asyncapi.yaml");
-    let input = serde_yaml::from_str::<serde_yaml::Value>(yaml_data).unwrap();
-    let input = serde_yaml::from_value::<HashMap<String, SchemaDef>>(
-        input["components"]["schemas"].clone(),
-    )
-    .unwrap();
-    let codegen = schema2code::generate_rust(input);
+    let dest_path = Path::new(&out_dir).join(file_name);
     std::fs::write(&dest_path, codegen).unwrap();
     Command::new("rustfmt")
         .arg(&dest_path)
         .output()
         .expect("Failed to format generated code");
 }
+
+fn main() {
+    let yaml_data = include_str!("./asyncapi.yaml");
+    generate(yaml_data, "asyncapi.rs", &GeneratorConfig::default());
+    generate(
+        yaml_data,
+        "asyncapi_unknown_enum.rs",
+        &GeneratorConfig {
+            unknown_enum_variant: true,
+            untagged_other_variant: true,
+            ..Default::default()
+        },
+    );
+    generate(
+        yaml_data,
+        "asyncapi_validated.rs",
+        &GeneratorConfig {
+            validate: true,
+            redact_write_only: true,
+            ..Default::default()
+        },
+    );
+    generate(
+        yaml_data,
+        "asyncapi_encapsulated.rs",
+        &GeneratorConfig {
+            encapsulate: true,
+            ..Default::default()
+        },
+    );
+    generate(
+        yaml_data,
+        "asyncapi_arc_str.rs",
+        &GeneratorConfig {
+            string_type: StringType::ArcStr,
+            ..Default::default()
+        },
+    );
+    generate(
+        yaml_data,
+        "asyncapi_json_schema.rs",
+        &GeneratorConfig {
+            derive_json_schema: true,
+            ..Default::default()
+        },
+    );
+    let messages = schema2code::generate_rust_from_messages_with(
+        include_str!("./messages.yaml"),
+        &GeneratorConfig {
+            message_enum: Some("Messages".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    write_formatted("messages.rs", messages);
+    generate(
+        include_str!("./floats.yaml"),
+        "floats.rs",
+        &GeneratorConfig {
+            bitwise_float_eq: true,
+            ..Default::default()
+        },
+    );
+    generate(
+        include_str!("./borrowed.yaml"),
+        "borrowed.rs",
+        &GeneratorConfig {
+            string_type: StringType::Cow,
+            ..Default::default()
+        },
+    );
+}
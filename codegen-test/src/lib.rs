@@ -1,7 +1,47 @@
+#[allow(clippy::all)]
 pub mod models {
     include!(concat!(env!("OUT_DIR"), "/asyncapi.rs"));
 }
 
+#[allow(clippy::all)]
+pub mod unknown_enum_models {
+    include!(concat!(env!("OUT_DIR"), "/asyncapi_unknown_enum.rs"));
+}
+
+#[allow(clippy::all)]
+pub mod validated_models {
+    include!(concat!(env!("OUT_DIR"), "/asyncapi_validated.rs"));
+}
+
+#[allow(clippy::all)]
+pub mod encapsulated_models {
+    include!(concat!(env!("OUT_DIR"), "/asyncapi_encapsulated.rs"));
+}
+
+#[allow(clippy::all)]
+pub mod arc_str_models {
+    include!(concat!(env!("OUT_DIR"), "/asyncapi_arc_str.rs"));
+}
+
+#[allow(clippy::all)]
+pub mod json_schema_models {
+    include!(concat!(env!("OUT_DIR"), "/asyncapi_json_schema.rs"));
+}
+
+pub mod message_models {
+    include!(concat!(env!("OUT_DIR"), "/messages.rs"));
+}
+
+#[allow(clippy::all)]
+pub mod float_models {
+    include!(concat!(env!("OUT_DIR"), "/floats.rs"));
+}
+
+#[allow(clippy::all)]
+pub mod borrowed_models {
+    include!(concat!(env!("OUT_DIR"), "/borrowed.rs"));
+}
+
 #[cfg(test)]
 mod test {
     pub use crate::models::*;
@@ -10,4 +50,544 @@ mod test {
     fn test() {
         let _x: SampleRequestPayload;
     }
+
+    #[test]
+    fn test_all_of_into_base() {
+        let employee: Employee =
+            serde_json::from_str(r#"{"name": "lurian", "company": "acme"}"#).unwrap();
+        assert_eq!(Person::from(&employee), Person::new("lurian".to_string()));
+        assert_eq!(EmployeeInfo::from(&employee).company, "acme");
+    }
+
+    #[test]
+    fn test_all_of_with_own_properties() {
+        let manager: Manager = serde_json::from_str(r#"{"name": "lurian", "reports": 3}"#).unwrap();
+        assert_eq!(Person::from(&manager), Person::new("lurian".to_string()));
+        assert_eq!(manager.manager_properties.reports, 3);
+        assert!(serde_json::from_str::<Manager>(r#"{"name": "lurian"}"#).is_err());
+    }
+
+    #[test]
+    fn test_recursive_tuple() {
+        let node: TreeNode =
+            serde_json::from_str(r#"{"name": "a", "next": ["b", {"name": "c"}]}"#).unwrap();
+        let (label, child) = node.next.unwrap();
+        assert_eq!(label, "b");
+        assert_eq!(child.name, "c");
+    }
+
+    #[test]
+    fn test_mixed_enum() {
+        for value in [r#""a""#, "1", "true"] {
+            serde_json::from_str::<MixedLiteral>(value).unwrap();
+        }
+        assert!(matches!(
+            serde_json::from_str::<MixedLiteral>("1").unwrap(),
+            MixedLiteral::Int1(_)
+        ));
+        for value in [r#""b""#, "2", "false", "null"] {
+            assert!(serde_json::from_str::<MixedLiteral>(value).is_err());
+        }
+    }
+
+    #[test]
+    fn test_enum_try_from() {
+        assert_eq!(
+            AccountStatus::try_from("Active").unwrap(),
+            AccountStatus::Active
+        );
+        assert!(AccountStatus::try_from("Suspended").is_err());
+        assert!(serde_json::from_str::<AccountStatus>(r#""Suspended""#).is_err());
+    }
+
+    #[test]
+    fn test_tagged_variants_declaring_the_tag() {
+        let json = r#"{"species":"cat","lives":9}"#;
+        let pet: Pet = serde_json::from_str(json).unwrap();
+        assert_eq!(pet, Pet::Cat(Cat::new(9)));
+        assert_eq!(pet.discriminator(), "cat");
+        assert_eq!(serde_json::to_string(&pet).unwrap(), json);
+        let pet: Pet = serde_json::from_str(r#"{"species":"dog","goodBoy":true}"#).unwrap();
+        assert_eq!(pet, Pet::Dog(Dog::new(true)));
+    }
+
+    #[test]
+    fn test_arc_str_fields() {
+        use crate::arc_str_models::Person;
+        let person: Person = serde_json::from_str(r#"{"name": "Jane"}"#).unwrap();
+        let name: std::sync::Arc<str> = person.name.clone();
+        assert_eq!(&*name, "Jane");
+        assert_eq!(
+            serde_json::to_string(&person).unwrap(),
+            r#"{"name":"Jane"}"#
+        );
+    }
+
+    #[test]
+    fn test_integer_enum_value() {
+        for value in [-1, 0, 5] {
+            let priority = Priority::try_from(value).unwrap();
+            assert_eq!(priority.value(), value);
+            assert_eq!(serde_json::to_string(&priority).unwrap(), value.to_string());
+        }
+        assert!(Priority::try_from(3).is_err());
+    }
+
+    #[test]
+    fn test_enum_names() {
+        assert_eq!(Severity::High as i64, 3);
+        assert_eq!(serde_json::to_string(&Severity::Low).unwrap(), "1");
+        let severity: Severity = serde_json::from_str("2").unwrap();
+        assert_eq!(severity, Severity::Medium);
+        assert!(serde_json::from_str::<Severity>("4").is_err());
+        let color: Color = serde_json::from_str(r##""#0f0""##).unwrap();
+        assert_eq!(color, Color::Green);
+        assert_eq!(serde_json::to_string(&Color::Red).unwrap(), r##""#f00""##);
+    }
+
+    #[test]
+    fn test_const_with_special_characters() {
+        let json = r#"{"text":"he said \"hi\" \\ bye"}"#;
+        let quote: Quote = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&quote).unwrap(), json);
+        assert!(serde_json::from_str::<Quote>(r#"{"text":"he said hi"}"#).is_err());
+    }
+
+    #[test]
+    fn test_display_template() {
+        let json = r#"{"firstName":"Ada","lastName":"Lovelace","email":"ada@example.com"}"#;
+        let signature: Signature = serde_json::from_str(json).unwrap();
+        assert_eq!(signature.to_string(), "Ada Lovelace <ada@example.com>");
+        let signature: Signature =
+            serde_json::from_str(r#"{"firstName":"Ada","lastName":"Lovelace"}"#).unwrap();
+        assert_eq!(signature.to_string(), "Ada Lovelace <>");
+    }
+
+    #[test]
+    fn test_nested_combinator() {
+        let json = r#"{"firstName":"Ada","lastName":"Lovelace","number":"555"}"#;
+        let alert: Alert = serde_json::from_str(json).unwrap();
+        assert_eq!(alert.signature.first_name, "Ada");
+        assert_eq!(
+            alert.alert_target,
+            AlertTarget::PhoneTarget(PhoneTarget::new("555".to_string()))
+        );
+        assert_eq!(serde_json::to_string(&alert).unwrap(), json);
+    }
+
+    #[test]
+    fn test_variadic_tuple() {
+        let json = r#"{"args":["run",1,"a","b"]}"#;
+        let command: Command = serde_json::from_str(json).unwrap();
+        assert_eq!(command.args.0, "run");
+        assert_eq!(command.args.1, 1);
+        assert_eq!(command.args.2, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(serde_json::to_string(&command).unwrap(), json);
+        let command: Command = serde_json::from_str(r#"{"args":["run",1]}"#).unwrap();
+        assert!(command.args.2.is_empty());
+        assert!(serde_json::from_str::<Command>(r#"{"args":["run"]}"#).is_err());
+    }
+
+    #[test]
+    fn test_enum_variant_rename() {
+        let state: TaskState = serde_json::from_str(r#""in-progress""#).unwrap();
+        assert_eq!(state, TaskState::InProgress);
+        assert_eq!(serde_json::to_string(&state).unwrap(), r#""in-progress""#);
+        assert_eq!(
+            serde_json::to_string(&TaskState::Done).unwrap(),
+            r#""done""#
+        );
+    }
+
+    #[test]
+    fn test_enum_unknown_variant() {
+        use crate::unknown_enum_models::AccountStatus;
+        let status: AccountStatus = serde_json::from_str(r#""Inactive""#).unwrap();
+        assert_eq!(status, AccountStatus::Inactive);
+        let status: AccountStatus = serde_json::from_str(r#""Suspended""#).unwrap();
+        assert_eq!(status, AccountStatus::Unknown("Suspended".to_string()));
+        assert_eq!(serde_json::to_string(&status).unwrap(), r#""Suspended""#);
+    }
+
+    #[test]
+    fn test_additional_properties_true() {
+        let json = r#"{"name":"widget","count":3,"color":"red","size":{"width":2}}"#;
+        let annotated: Annotated = serde_json::from_str(json).unwrap();
+        assert_eq!(annotated.name, "widget");
+        assert_eq!(annotated.count, Some(3));
+        assert_eq!(annotated.additional_properties.len(), 2);
+        assert_eq!(annotated.additional_properties["color"], "red");
+        assert_eq!(
+            serde_json::to_value(&annotated).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_empty_additional_properties() {
+        let json = r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8"}"#;
+        let request: RequestBase = serde_json::from_str(json).unwrap();
+        assert!(request.additional_properties.is_empty());
+        let serialized = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            serde_json::from_str::<RequestBase>(&serialized).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn test_array_constraints() {
+        use crate::validated_models::TagList;
+        let tags: TagList = serde_json::from_str(r#"{"tags": ["a", "b"]}"#).unwrap();
+        assert_eq!(tags.labels, None);
+        serde_json::from_str::<TagList>(r#"{"tags": ["a"], "labels": ["b"]}"#).unwrap();
+        assert!(serde_json::from_str::<TagList>(r#"{"tags": []}"#).is_err());
+        assert!(serde_json::from_str::<TagList>(r#"{"tags": ["a"], "labels": []}"#).is_err());
+        assert!(serde_json::from_str::<TagList>(r#"{"tags": ["a", "b", "c", "d"]}"#).is_err());
+        assert!(serde_json::from_str::<TagList>(r#"{"tags": ["a", "a"]}"#).is_err());
+        // without validation the constraints are not enforced
+        serde_json::from_str::<crate::models::TagList>(r#"{"tags": []}"#).unwrap();
+    }
+
+    #[test]
+    fn test_validate() {
+        use crate::validated_models::{Checkout, TagList, ValidationError};
+        let mut tags = TagList::new(vec!["a".to_string()]);
+        assert_eq!(tags.validate(), Ok(()));
+        tags.tags = ["a", "b", "c", "d"].map(String::from).to_vec();
+        let error = tags.validate().unwrap_err();
+        assert_eq!(error.field, "tags");
+        assert!(error.to_string().contains("at most 3"), "{}", error);
+        tags.tags.truncate(1);
+        tags.labels = Some(vec![]);
+        assert_eq!(tags.validate().unwrap_err().field, "labels");
+
+        let mut checkout = Checkout::new(5);
+        checkout.credit_card = Some("4111".to_string());
+        assert_eq!(
+            checkout.validate(),
+            Err(ValidationError {
+                field: "billing_address".to_string(),
+                message: "`billing_address` is required when `credit_card` is present".to_string(),
+            })
+        );
+        checkout.billing_address = Some("Main St".to_string());
+        assert_eq!(checkout.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_property_names() {
+        use crate::validated_models::Labels;
+        let labels: Labels = serde_json::from_str(r#"{"env": "prod"}"#).unwrap();
+        assert_eq!(labels.0["env"], "prod");
+        let error = serde_json::from_str::<Labels>(r#"{"Env": "prod"}"#).unwrap_err();
+        assert!(error.to_string().contains("`Env`"), "{}", error);
+        // without validation the constraint is not enforced
+        serde_json::from_str::<crate::models::Labels>(r#"{"Env": "prod"}"#).unwrap();
+    }
+
+    #[test]
+    fn test_property_count() {
+        use crate::validated_models::Labels;
+        let error = serde_json::from_str::<Labels>("{}").unwrap_err();
+        assert!(
+            error.to_string().contains("at least 1 properties"),
+            "{}",
+            error
+        );
+        let error = serde_json::from_str::<Labels>(r#"{"a": "1", "b": "2", "c": "3", "d": "4"}"#)
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("at most 3 properties"),
+            "{}",
+            error
+        );
+        serde_json::from_str::<crate::models::Labels>("{}").unwrap();
+    }
+
+    #[test]
+    fn test_dependent_required() {
+        use crate::validated_models::Checkout;
+        let error = serde_json::from_str::<Checkout>(r#"{"amount": 5, "credit_card": "4111"}"#)
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("`billing_address` is required when `credit_card` is present"),
+            "{}",
+            error
+        );
+        let checkout: Checkout = serde_json::from_str(
+            r#"{"amount": 5, "credit_card": "4111", "billing_address": "Main St"}"#,
+        )
+        .unwrap();
+        assert_eq!(checkout.billing_address.as_deref(), Some("Main St"));
+        serde_json::from_str::<Checkout>(r#"{"amount": 5}"#).unwrap();
+        serde_json::from_str::<crate::models::Checkout>(r#"{"amount": 5, "credit_card": "4111"}"#)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_not() {
+        use crate::validated_models::Account;
+        let account: Account =
+            serde_json::from_str(r#"{"username": "lurian", "level": 1}"#).unwrap();
+        assert_eq!(account.level, Some(1));
+        let error = serde_json::from_str::<Account>(r#"{"username": "forbidden"}"#).unwrap_err();
+        assert!(error.to_string().contains("must not be"), "{}", error);
+        assert!(serde_json::from_str::<Account>(r#"{"username": "a", "level": 13}"#).is_err());
+        serde_json::from_str::<crate::models::Account>(r#"{"username": "forbidden"}"#).unwrap();
+    }
+
+    #[test]
+    fn test_object_const() {
+        let json = r#"{"major":1,"name":"v1","stable":true,"origin":{"vendor":"acme"}}"#;
+        let version: ProtocolVersion = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&version).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        );
+        let wrong = r#"{"major":2,"name":"v1","stable":true,"origin":{"vendor":"acme"}}"#;
+        assert!(serde_json::from_str::<ProtocolVersion>(wrong).is_err());
+        let wrong = r#"{"major":1,"name":"v1","stable":true,"origin":{"vendor":"other"}}"#;
+        assert!(serde_json::from_str::<ProtocolVersion>(wrong).is_err());
+    }
+
+    #[test]
+    fn test_skip_serializing_none() {
+        let tags = TagList {
+            tags: vec!["a".to_string()],
+            labels: None,
+        };
+        assert_eq!(serde_json::to_string(&tags).unwrap(), r#"{"tags":["a"]}"#);
+    }
+
+    #[test]
+    fn test_decimal() {
+        let payment: Payment =
+            serde_json::from_str(r#"{"amount": "12.345678901234567890"}"#).unwrap();
+        assert_eq!(payment.amount.to_string(), "12.345678901234567890");
+    }
+
+    #[test]
+    fn test_discriminator() {
+        let shape: Shape = serde_json::from_str(r#"{"kind": "Square", "side": 2}"#).unwrap();
+        assert!(matches!(shape, Shape::Square(_)));
+        assert_eq!(shape.discriminator(), "Square");
+        let circle = Shape::Circle(Circle { radius: 1 });
+        assert_eq!(circle.discriminator(), "Circle");
+        let json = serde_json::to_value(&circle).unwrap();
+        assert_eq!(json["kind"], circle.discriminator());
+    }
+
+    #[test]
+    fn test_one_of_try_into_variant() {
+        let shape: Shape = serde_json::from_str(r#"{"kind": "Square", "side": 2}"#).unwrap();
+        let square: Square = shape.clone().try_into().unwrap();
+        assert_eq!(square.side, 2);
+        let not_a_circle: Result<Circle, Shape> = shape.clone().try_into();
+        assert_eq!(not_a_circle, Err(shape));
+    }
+
+    #[test]
+    fn test_map_newtype() {
+        let scores: Scores = serde_json::from_str(r#"{"alice": 3, "bob": 5}"#).unwrap();
+        assert_eq!(scores.0.get("bob"), Some(&5));
+        let json = serde_json::to_value(&scores).unwrap();
+        assert_eq!(json, serde_json::json!({"alice": 3, "bob": 5}));
+    }
+
+    #[test]
+    fn test_discriminator_mapping() {
+        let command: UserCommand =
+            serde_json::from_str(r#"{"action": "lookupUser", "userId": "1"}"#).unwrap();
+        let UserCommand::LookupUser(ref lookup_user) = command else {
+            panic!("Expected LookupUser, got {:?}", command);
+        };
+        assert_eq!(lookup_user.user_id, "1");
+        assert_eq!(command.discriminator(), "lookupUser");
+        let command: UserCommand =
+            serde_json::from_str(r#"{"action": "setScores", "alice": 1}"#).unwrap();
+        assert_eq!(command.discriminator(), "setScores");
+        let json = serde_json::to_value(&command).unwrap();
+        assert_eq!(json, serde_json::json!({"action": "setScores", "alice": 1}));
+    }
+
+    #[test]
+    fn test_tagged_enum_camel_case_fields() {
+        let json = serde_json::json!({"action": "lookupUser", "userId": "1"});
+        let command: UserCommand = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&command).unwrap(), json);
+        let UserCommand::LookupUser(lookup_user) = command else {
+            panic!("Expected LookupUser");
+        };
+        assert_eq!(lookup_user.user_id, "1");
+    }
+
+    #[test]
+    fn test_base64_bytes() {
+        let payment: Payment =
+            serde_json::from_str(r#"{"amount": "1", "receipt": "aGVsbG8="}"#).unwrap();
+        assert_eq!(payment.receipt.as_deref(), Some(&b"hello"[..]));
+        let json = serde_json::to_value(&payment).unwrap();
+        assert_eq!(json["receipt"], "aGVsbG8=");
+        let payment: Payment = serde_json::from_str(r#"{"amount": "1"}"#).unwrap();
+        assert!(payment.receipt.is_none());
+        assert!(serde_json::from_str::<Payment>(r#"{"amount": "1", "receipt": "%%"}"#).is_err());
+        let payment: Payment =
+            serde_json::from_str(r#"{"amount": "1", "signature": "c2ln"}"#).unwrap();
+        assert_eq!(payment.signature.as_deref(), Some(&b"sig"[..]));
+    }
+
+    #[test]
+    fn test_bitwise_float_eq() {
+        use crate::float_models::Measurement;
+        let json = r#"{"value": 0.1, "error": 0.5, "unit": "m"}"#;
+        let a: Measurement = serde_json::from_str(json).unwrap();
+        let b: Measurement = serde_json::from_str(json).unwrap();
+        assert_eq!(a, b);
+        let nan = Measurement {
+            value: f64::NAN,
+            error: None,
+            unit: "m".to_string(),
+        };
+        assert_eq!(nan, nan.clone());
+        assert_ne!(a, Measurement { value: 0.2, ..b });
+    }
+
+    #[test]
+    fn test_bitwise_float_eq_nested_float() {
+        use crate::float_models::Series;
+        // a float in a `Vec` is compared with `==`, so `Series` can't claim to be `Eq`
+        let series = Series {
+            samples: vec![1.0, f64::NAN],
+        };
+        assert_ne!(series, series.clone());
+        assert_eq!(
+            Series {
+                samples: vec![1.0, 2.0]
+            },
+            Series {
+                samples: vec![1.0, 2.0]
+            }
+        );
+    }
+
+    #[test]
+    fn test_example_constructor() {
+        let example = serde_json::json!({
+            "handle": "lur1an",
+            "age": 30,
+            "status": "Active",
+            "address": {"city": "Berlin"},
+            "nicknames": ["lu"]
+        });
+        assert_eq!(
+            Profile::example(),
+            serde_json::from_value::<Profile>(example).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_type_array() {
+        let reading: Reading = serde_json::from_str(r#"{"note": null, "value": 3}"#).unwrap();
+        assert_eq!(reading.note, None);
+        assert_eq!(reading.value, ReadingValue::Int(3));
+        let reading: Reading = serde_json::from_str(r#"{"note": "ok", "value": "three"}"#).unwrap();
+        assert_eq!(reading.note.as_deref(), Some("ok"));
+        assert_eq!(reading.value, ReadingValue::String("three".to_string()));
+        assert!(serde_json::from_str::<Reading>(r#"{"note": null, "value": true}"#).is_err());
+    }
+
+    #[test]
+    fn test_untagged_other_variant() {
+        use crate::unknown_enum_models::Contact;
+        let contact: Contact = serde_json::from_str(r#"{"email": "a@b.c"}"#).unwrap();
+        assert!(matches!(contact, Contact::EmailContact(_)));
+        let payload = serde_json::json!({"pager": 42});
+        let contact: Contact = serde_json::from_value(payload.clone()).unwrap();
+        assert_eq!(contact, Contact::Other(payload.clone()));
+        assert_eq!(serde_json::to_value(&contact).unwrap(), payload);
+        assert!(serde_json::from_value::<crate::models::Contact>(payload).is_err());
+    }
+
+    #[test]
+    fn test_constructor() {
+        let tags = TagList::new(vec!["a".to_string()]);
+        assert_eq!(tags.tags, ["a"]);
+        assert_eq!(tags.labels, None);
+        let request = RequestBase::new(uuid::Uuid::nil());
+        assert_eq!(request.kind, None);
+        assert!(request.additional_properties.is_empty());
+    }
+
+    #[test]
+    fn test_encapsulate() {
+        use crate::encapsulated_models::{RequestBase, TagList};
+        let mut tags = TagList::new(vec!["a".to_string()]);
+        assert_eq!(tags.tags(), &["a"]);
+        tags.labels_mut().replace(vec!["b".to_string()]);
+        assert_eq!(tags.labels().as_deref(), Some(&["b".to_string()][..]));
+        let json = r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8","kind":"request"}"#;
+        let request: RequestBase = serde_json::from_str(json).unwrap();
+        assert!(request.kind().is_some());
+        assert!(request.additional_properties().is_empty());
+    }
+
+    #[test]
+    fn test_message_enum() {
+        use crate::message_models::*;
+        let message = Messages::from_json(r#"{"userId": "1", "name": "lurian"}"#).unwrap();
+        assert!(matches!(message, Messages::UserCreated(ref user) if user.user_id == "1"));
+        let message = Messages::from_json(r#"{"orderId": 7, "amount": 3}"#).unwrap();
+        let Messages::OrderPlaced(order) = message else {
+            panic!("Expected OrderPlaced, got {:?}", message);
+        };
+        assert_eq!(order.order_id, 7);
+        assert!(Messages::from_json(r#"{"unrelated": true}"#).is_err());
+    }
+
+    #[test]
+    fn test_redact_write_only() {
+        let json = r#"{"username": "lur1an", "password": "hunter2"}"#;
+        let credentials: crate::validated_models::Credentials = serde_json::from_str(json).unwrap();
+        let debug = format!("{:?}", credentials);
+        assert!(debug.contains("lur1an"));
+        assert!(debug.contains("[redacted]"));
+        assert!(!debug.contains("hunter2"));
+        assert_eq!(credentials.password, "hunter2");
+        let credentials: Credentials = serde_json::from_str(json).unwrap();
+        assert!(format!("{:?}", credentials).contains("hunter2"));
+    }
+
+    #[test]
+    fn test_json_schema_round_trips_field_names() {
+        use crate::json_schema_models::{Dog, ProtocolVersion};
+        let schema = serde_json::to_value(schemars::schema_for!(Dog)).unwrap();
+        let serialized = serde_json::to_value(Dog::new(true)).unwrap();
+        assert_eq!(
+            schema["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            serialized.as_object().unwrap().keys().collect::<Vec<_>>()
+        );
+        assert_eq!(schema["required"], serde_json::json!(["goodBoy"]));
+        let schema = serde_json::to_value(schemars::schema_for!(ProtocolVersion)).unwrap();
+        assert_eq!(schema["properties"]["name"]["const"], "v1");
+        assert_eq!(schema["properties"]["major"]["const"], 1);
+    }
+
+    #[test]
+    fn test_cow_borrows_strings() {
+        use crate::borrowed_models::LogLine;
+        use std::borrow::Cow;
+        let json = r#"{"level": "info", "message": "with \"escapes\"", "labels": ["a"]}"#;
+        let line: LogLine = serde_json::from_str(json).unwrap();
+        assert!(matches!(line.level, Cow::Borrowed("info")));
+        // escaped strings can't be borrowed and are copied instead
+        assert!(matches!(line.message, Cow::Owned(ref message) if message == "with \"escapes\""));
+        assert_eq!(line.labels.unwrap(), vec!["a"]);
+    }
 }
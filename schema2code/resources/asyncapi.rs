@@ -0,0 +1,171 @@
+#[derive(Debug, Clone, Eq, PartialEq, serde :: Deserialize, serde :: Serialize)]
+pub struct AnonymousEntity1 {
+    #[serde(rename = "child", skip_serializing_if = "Option::is_none")]
+    pub child: Option<Vec<serde_json::Value>>,
+    #[serde(rename = "event")]
+    pub event: monostate::MustBe!("someRandomVALUE"),
+    #[serde(rename = "data")]
+    pub data: DeleteUserData,
+}
+impl AnonymousEntity1 {
+    #[doc = r" Creates a value from the required fields, the optional ones are `None`"]
+    pub fn new(data: DeleteUserData) -> Self {
+        Self {
+            child: None,
+            event: Default::default(),
+            data,
+        }
+    }
+}
+#[derive(Debug, Clone, Eq, PartialEq, serde :: Deserialize, serde :: Serialize)]
+pub struct AnonymousEntity2 {
+    #[serde(rename = "event")]
+    pub event: monostate::MustBe!("deezNuts"),
+    #[serde(rename = "data")]
+    pub data: GetUserData,
+}
+impl AnonymousEntity2 {
+    #[doc = r" Creates a value from the required fields, the optional ones are `None`"]
+    pub fn new(data: GetUserData) -> Self {
+        Self {
+            event: Default::default(),
+            data,
+        }
+    }
+}
+#[derive(Debug, Clone, Eq, PartialEq, serde :: Deserialize, serde :: Serialize)]
+pub enum AnonymousEnum1 {
+    #[serde(rename = "active")]
+    Active,
+    #[serde(rename = "suspended")]
+    Suspended,
+}
+impl TryFrom<&str> for AnonymousEnum1 {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "active" => Ok(Self::Active),
+            "suspended" => Ok(Self::Suspended),
+            _ => Err(format!("unknown AnonymousEnum1 value: {}", value)),
+        }
+    }
+}
+#[derive(Debug, Clone, Eq, PartialEq, serde :: Deserialize, serde :: Serialize)]
+pub struct DeleteUser {
+    #[serde(flatten)]
+    pub request_base: RequestBase,
+    #[serde(flatten)]
+    pub anonymous_entity1: AnonymousEntity1,
+}
+impl From<&DeleteUser> for RequestBase {
+    fn from(value: &DeleteUser) -> Self {
+        value.request_base.clone()
+    }
+}
+impl From<&DeleteUser> for AnonymousEntity1 {
+    fn from(value: &DeleteUser) -> Self {
+        value.anonymous_entity1.clone()
+    }
+}
+#[derive(Debug, Clone, Eq, PartialEq, serde :: Deserialize, serde :: Serialize)]
+pub struct DeleteUserData {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    #[serde(rename = "userName")]
+    pub user_name: String,
+}
+impl DeleteUserData {
+    #[doc = r" Creates a value from the required fields, the optional ones are `None`"]
+    pub fn new(user_id: String, user_name: String) -> Self {
+        Self { user_id, user_name }
+    }
+}
+#[derive(Debug, Clone, Eq, PartialEq, serde :: Deserialize, serde :: Serialize)]
+#[serde(untagged)]
+pub enum GetUser {
+    RequestBase(RequestBase),
+    AnonymousEntity2(AnonymousEntity2),
+}
+impl TryFrom<GetUser> for RequestBase {
+    type Error = GetUser;
+    fn try_from(value: GetUser) -> Result<Self, Self::Error> {
+        match value {
+            GetUser::RequestBase(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+}
+impl TryFrom<GetUser> for AnonymousEntity2 {
+    type Error = GetUser;
+    fn try_from(value: GetUser) -> Result<Self, Self::Error> {
+        match value {
+            GetUser::AnonymousEntity2(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+}
+#[derive(Debug, Clone, Eq, PartialEq, serde :: Deserialize, serde :: Serialize)]
+pub struct GetUserData {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    #[serde(rename = "status", skip_serializing_if = "Option::is_none")]
+    pub status: Option<AnonymousEnum1>,
+}
+impl GetUserData {
+    #[doc = r" Creates a value from the required fields, the optional ones are `None`"]
+    pub fn new(user_id: String) -> Self {
+        Self {
+            user_id,
+            status: None,
+        }
+    }
+}
+#[derive(Debug, Clone, Eq, PartialEq, serde :: Deserialize, serde :: Serialize)]
+pub struct RequestBase {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "kind")]
+    pub kind: monostate::MustBe!("request"),
+}
+impl RequestBase {
+    #[doc = r" Creates a value from the required fields, the optional ones are `None`"]
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            kind: Default::default(),
+        }
+    }
+}
+#[derive(Debug, Clone, Eq, PartialEq, serde :: Deserialize, serde :: Serialize)]
+#[serde(tag = "event")]
+pub enum SampleRequestPayload {
+    GetUser(GetUser),
+    DeleteUser(DeleteUser),
+}
+impl SampleRequestPayload {
+    #[doc = r" The value of the discriminator field that selects this variant"]
+    pub fn discriminator(&self) -> &'static str {
+        match self {
+            Self::GetUser(_) => "GetUser",
+            Self::DeleteUser(_) => "DeleteUser",
+        }
+    }
+}
+impl TryFrom<SampleRequestPayload> for GetUser {
+    type Error = SampleRequestPayload;
+    fn try_from(value: SampleRequestPayload) -> Result<Self, Self::Error> {
+        match value {
+            SampleRequestPayload::GetUser(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+}
+impl TryFrom<SampleRequestPayload> for DeleteUser {
+    type Error = SampleRequestPayload;
+    fn try_from(value: SampleRequestPayload) -> Result<Self, Self::Error> {
+        match value {
+            SampleRequestPayload::DeleteUser(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+}
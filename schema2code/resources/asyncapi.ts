@@ -0,0 +1,33 @@
+export interface AnonymousEntity1 {
+  child?: Array<unknown>;
+  event: "someRandomVALUE";
+  data: DeleteUserData;
+}
+
+export interface AnonymousEntity2 {
+  event: "deezNuts";
+  data: GetUserData;
+}
+
+export type AnonymousEnum1 = "active" | "suspended";
+
+export type DeleteUser = RequestBase & AnonymousEntity1;
+
+export interface DeleteUserData {
+  userId: string;
+  userName: string;
+}
+
+export type GetUser = RequestBase | AnonymousEntity2;
+
+export interface GetUserData {
+  userId: string;
+  status?: AnonymousEnum1;
+}
+
+export interface RequestBase {
+  id: string;
+  kind: "request";
+}
+
+export type SampleRequestPayload = ({ event: "GetUser" } & GetUser) | ({ event: "DeleteUser" } & DeleteUser);
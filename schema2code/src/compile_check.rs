@@ -0,0 +1,134 @@
+use std::{fs, path::PathBuf, process::Command};
+
+/// Dependencies the generated Rust code can refer to
+const SCAFFOLD_MANIFEST: &str = r#"[package]
+name = "generated"
+version = "0.0.0"
+edition = "2021"
+
+[workspace]
+
+[lib]
+path = "lib.rs"
+
+[dependencies]
+monostate = "0.1"
+serde = { version = "1", features = ["derive", "rc"] }
+serde_json = "1"
+uuid = { version = "1", features = ["serde"] }
+"#;
+
+/// Runs `cargo check` over `generated` in a throwaway crate that depends on everything the
+/// generators emit code for, returning the compiler output if it doesn't build.
+/// Build artifacts are kept in a shared target directory in the system's temp dir so that
+/// consecutive checks don't rebuild the dependencies.
+pub fn check_compiles(generated: &str) -> Result<(), String> {
+    let dir = tempfile::tempdir().map_err(|e| e.to_string())?;
+    fs::write(dir.path().join("Cargo.toml"), scaffold_manifest(generated))
+        .map_err(|e| e.to_string())?;
+    fs::write(dir.path().join("lib.rs"), generated).map_err(|e| e.to_string())?;
+    run_cargo(&dir, &["check", "--quiet"])
+}
+
+/// Like `check_compiles` but also runs `main`, the body of a `main` function that can use the
+/// generated types (e.g. to round-trip a value through a format), returning the compiler output
+/// or what the program printed to stderr if it fails. `bincode` (1.x) is available to it.
+pub fn check_runs(generated: &str, main: &str) -> Result<(), String> {
+    let dir = tempfile::tempdir().map_err(|e| e.to_string())?;
+    let mut manifest = scaffold_manifest(generated);
+    if uses_crate(main, "bincode") {
+        manifest.push_str("bincode = \"1\"\n");
+    }
+    manifest.push_str("\n[[bin]]\nname = \"main\"\npath = \"main.rs\"\n");
+    fs::write(dir.path().join("Cargo.toml"), manifest).map_err(|e| e.to_string())?;
+    fs::write(dir.path().join("lib.rs"), generated).map_err(|e| e.to_string())?;
+    let main = format!(
+        "#[allow(unused_imports)]\nuse generated::*;\n\nfn main() {{\n{}\n}}\n",
+        main
+    );
+    fs::write(dir.path().join("main.rs"), main).map_err(|e| e.to_string())?;
+    run_cargo(&dir, &["run", "--quiet", "--bin", "main"])
+}
+
+/// The manifest of the throwaway crate, with the optional dependencies `generated` refers to
+fn scaffold_manifest(generated: &str) -> String {
+    let mut manifest = SCAFFOLD_MANIFEST.to_string();
+    if uses_crate(generated, "rust_decimal") {
+        manifest.push_str("rust_decimal = { version = \"1\", features = [\"serde-str\"] }\n");
+    }
+    if uses_crate(generated, "base64") {
+        manifest.push_str("base64 = \"0.22\"\n");
+    }
+    if uses_crate(generated, "indexmap") {
+        manifest.push_str("indexmap = { version = \"2\", features = [\"serde\"] }\n");
+    }
+    if uses_crate(generated, "compact_str") {
+        manifest.push_str("compact_str = { version = \"0.8\", features = [\"serde\"] }\n");
+    }
+    if uses_crate(generated, "schemars") {
+        let mut features = vec!["\"uuid1\""];
+        if uses_crate(generated, "rust_decimal") {
+            features.push("\"rust_decimal1\"");
+        }
+        if uses_crate(generated, "indexmap") {
+            features.push("\"indexmap2\"");
+        }
+        manifest.push_str(&format!(
+            "schemars = {{ version = \"1\", features = [{}] }}\n",
+            features.join(", ")
+        ));
+    }
+    if uses_crate(generated, "sqlx") {
+        let mut features = vec!["\"postgres\"", "\"uuid\""];
+        if uses_crate(generated, "rust_decimal") {
+            features.push("\"rust_decimal\"");
+        }
+        manifest.push_str(&format!(
+            "sqlx = {{ version = \"0.8\", features = [{}] }}\n",
+            features.join(", ")
+        ));
+    }
+    if uses_crate(generated, "regex") {
+        manifest.push_str("regex = \"1\"\n");
+    }
+    manifest
+}
+
+/// Runs cargo with `args` on the throwaway crate in `dir`
+fn run_cargo(dir: &tempfile::TempDir, args: &[&str]) -> Result<(), String> {
+    let target_dir: PathBuf = std::env::temp_dir().join("schema2code-check-target");
+    let output = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .args(args)
+        .arg("--manifest-path")
+        .arg(dir.path().join("Cargo.toml"))
+        .env("CARGO_TARGET_DIR", target_dir)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+}
+
+/// Whether `generated` refers to a path in `krate`, whether or not it was run through rustfmt
+fn uses_crate(generated: &str, krate: &str) -> bool {
+    generated.contains(&format!("{krate}::")) || generated.contains(&format!("{krate} ::"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::check_compiles;
+
+    #[test]
+    fn test_resources_asyncapi_compiles() {
+        let code = crate::generate_rust(include_str!("../resources/asyncapi.yaml")).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+    }
+
+    #[test]
+    fn test_invalid_code_is_reported() {
+        let stderr = check_compiles("pub struct Broken { field: Missing }").unwrap_err();
+        assert!(stderr.contains("Missing"), "{}", stderr);
+    }
+}
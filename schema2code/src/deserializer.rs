@@ -1,7 +1,8 @@
+use indexmap::IndexMap;
+use monostate::MustBe;
 use std::collections::HashMap;
 
-use monostate::MustBe;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct SchemaRef {
@@ -10,11 +11,34 @@ pub struct SchemaRef {
 }
 
 impl SchemaRef {
-    pub fn get_schema_name(&self) -> &str {
-        self.schema_path
-            .split('/')
-            .last()
+    /// Derives a type name from the JSON pointer of the ref, structural segments are skipped, e.g.
+    /// `#/components/schemas/Foo` -> `Foo`, `#/definitions/Baz` -> `Baz` and
+    /// `#/components/schemas/Foo/properties/bar` -> `FooBar`
+    pub fn get_schema_name(&self) -> String {
+        let mut segments = self
+            .schema_path
+            .split('#')
+            .next_back()
             .expect("Incorrect Ref Path")
+            .split('/')
+            .filter(|segment| {
+                !matches!(
+                    *segment,
+                    "" | "components"
+                        | "schemas"
+                        | "definitions"
+                        | "$defs"
+                        | "properties"
+                        | "items"
+                )
+            });
+        let mut name = segments.next().unwrap_or_default().to_string();
+        for segment in segments {
+            let (first, rest) = segment.split_at(1);
+            name.push_str(&first.to_uppercase());
+            name.push_str(rest);
+        }
+        name
     }
 }
 
@@ -28,11 +52,19 @@ pub enum SchemaType {
     Array,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum Format {
+    Int8,
+    Int16,
     Int32,
     Int64,
+    Int128,
+    Uint8,
+    Uint16,
+    Uint32,
+    Uint64,
+    Uint128,
     Float,
     Double,
     Byte,
@@ -41,9 +73,17 @@ pub enum Format {
     Uuid,
     #[serde(rename = "date-time")]
     DateTime,
+    Decimal,
+    /// A custom or vendor format this generator doesn't know, e.g. `currency`, the schema falls
+    /// back to its base type
+    #[serde(untagged)]
+    Other(String),
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+/// The keywords of a primitive schema that decide its kind. `const` takes precedence over `enum`,
+/// which takes precedence over the plain type, e.g. `{const: a, enum: [a, b]}` is a `Const`.
+/// A `const` or `enum` whose values don't fit the type is an error rather than a `Basic` schema.
+#[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
 pub enum PrimitiveType<T> {
@@ -61,6 +101,29 @@ pub enum PrimitiveType<T> {
     },
 }
 
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for PrimitiveType<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Keywords<T> {
+            #[serde(rename = "const")]
+            const_value: Option<T>,
+            #[serde(rename = "enum")]
+            enum_values: Option<Vec<T>>,
+            format: Option<Format>,
+            minimum: Option<T>,
+        }
+        let keywords = Keywords::<T>::deserialize(deserializer)?;
+        Ok(match (keywords.const_value, keywords.enum_values) {
+            (Some(const_value), _) => PrimitiveType::Const { const_value },
+            (None, Some(enum_values)) => PrimitiveType::Enum { enum_values },
+            (None, None) => PrimitiveType::Basic {
+                format: keywords.format,
+                minimum: keywords.minimum,
+            },
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
@@ -78,7 +141,18 @@ impl Default for AdditionalProperties {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum SchemaDef {
+    /// Comes before `Object`, which would take an `allOf` with `type: object` and drop the members
+    AllOf {
+        title: Option<String>,
+        #[serde(rename = "allOf")]
+        all_of: Vec<Schema>,
+        /// The schema's own properties next to the ones it combines, merged into the composite
+        properties: Option<IndexMap<String, Schema>>,
+        #[serde(default)]
+        required: Vec<String>,
+    },
     Object {
         title: Option<String>,
         #[serde(rename = "type")]
@@ -86,80 +160,331 @@ pub enum SchemaDef {
         #[serde(default)]
         #[serde(rename = "additionalProperties")]
         additional_properties: AdditionalProperties,
-        properties: Option<HashMap<String, Schema>>,
+        properties: Option<IndexMap<String, Schema>>,
         #[serde(default)]
         required: Vec<String>,
+        /// A fixed value for the whole object
+        #[serde(rename = "const")]
+        const_value: Option<serde_json::Value>,
+        example: Option<serde_json::Value>,
+        #[serde(default)]
+        examples: Vec<serde_json::Value>,
+        #[serde(rename = "propertyNames")]
+        property_names: Option<PropertyNames>,
+        #[serde(rename = "minProperties")]
+        min_properties: Option<u64>,
+        #[serde(rename = "maxProperties")]
+        max_properties: Option<u64>,
+        /// Properties that have to be present whenever the property they're listed under is
+        #[serde(default)]
+        #[serde(rename = "dependentRequired")]
+        dependent_required: IndexMap<String, Vec<String>>,
+        #[serde(flatten)]
+        extensions: Extensions,
     },
     String {
         #[serde(rename = "type")]
         schema_type: MustBe!("string"),
         #[serde(flatten)]
         type_def: PrimitiveType<String>,
+        #[serde(default)]
+        #[serde(rename = "writeOnly")]
+        write_only: bool,
+        /// How the string encodes its content, e.g. `base64` for binary data
+        #[serde(rename = "contentEncoding")]
+        content_encoding: Option<String>,
+        not: Option<Not>,
+        #[serde(flatten)]
+        extensions: Extensions,
     },
     Integer {
         #[serde(rename = "type")]
         schema_type: MustBe!("integer"),
         #[serde(flatten)]
-        type_def: PrimitiveType<i64>,
+        type_def: PrimitiveType<IntegerValue>,
+        #[serde(default)]
+        #[serde(rename = "writeOnly")]
+        write_only: bool,
+        not: Option<Not>,
+        #[serde(flatten)]
+        extensions: Extensions,
     },
     Boolean {
         #[serde(rename = "type")]
         schema_type: MustBe!("boolean"),
+        #[serde(flatten)]
+        extensions: Extensions,
     },
     Number {
         #[serde(rename = "type")]
         schema_type: MustBe!("number"),
         #[serde(flatten)]
         type_def: PrimitiveType<f64>,
+        #[serde(default)]
+        #[serde(rename = "writeOnly")]
+        write_only: bool,
+        not: Option<Not>,
+        #[serde(flatten)]
+        extensions: Extensions,
     },
-    Array {
-        #[serde(rename = "type")]
-        schema_type: MustBe!("array"),
-        items: Option<Box<Schema>>,
-    },
+    /// Comes before `Array`, which would take a tuple's `items` schema and ignore `prefixItems`
     Tuple {
         #[serde(rename = "type")]
         schema_type: MustBe!("array"),
-        items: MustBe!(false),
+        items: TupleItems,
         #[serde(rename = "prefixItems")]
         prefix_items: Vec<Schema>,
     },
-    AllOf {
-        title: Option<String>,
-        #[serde(rename = "allOf")]
-        all_of: Vec<Schema>,
+    Array {
+        #[serde(rename = "type")]
+        schema_type: MustBe!("array"),
+        items: Option<Box<Schema>>,
+        #[serde(rename = "minItems")]
+        min_items: Option<u64>,
+        #[serde(rename = "maxItems")]
+        max_items: Option<u64>,
+        #[serde(default)]
+        #[serde(rename = "uniqueItems")]
+        unique_items: bool,
+        #[serde(flatten)]
+        extensions: Extensions,
     },
     OneOf {
         title: Option<String>,
         #[serde(rename = "oneOf")]
         one_of: Vec<Schema>,
-        discriminator: Option<String>,
+        discriminator: Option<Discriminator>,
     },
     AnyOf {
         title: Option<String>,
         #[serde(rename = "anyOf")]
         any_of: Vec<Schema>,
     },
+    /// An `enum` without a `type`, the values can be of mixed types, e.g. `["active", 1, true]`
+    Enum {
+        title: Option<String>,
+        #[serde(rename = "enum")]
+        enum_values: Vec<serde_json::Value>,
+        #[serde(flatten)]
+        extensions: Extensions,
+    },
+    /// A `type` listing several primitive types, e.g. `[string, "null"]` for a nullable string
+    MultiType {
+        title: Option<String>,
+        #[serde(rename = "type")]
+        types: Vec<TypeName>,
+        format: Option<Format>,
+    },
+}
+
+/// The schema every key of an object has to match, only the `pattern` is supported
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PropertyNames {
+    pub pattern: Option<String>,
+}
+
+/// The primitive types that can be combined in a `type` array
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum TypeName {
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Null,
+}
+
+/// The `discriminator` of a `oneOf`, either just the name of the tag property like in asyncapi or
+/// an object with a `mapping` from tag values to the variant schemas like in openapi
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum Discriminator {
+    PropertyName(String),
+    Object {
+        #[serde(rename = "propertyName")]
+        property_name: String,
+        #[serde(default)]
+        mapping: IndexMap<String, String>,
+    },
+}
+
+impl Discriminator {
+    pub fn property_name(&self) -> &str {
+        match self {
+            Discriminator::PropertyName(property_name)
+            | Discriminator::Object { property_name, .. } => property_name,
+        }
+    }
+
+    /// Finds the tag value that `mapping` assigns to the variant at `index` of the `oneOf`, the
+    /// mapping targets either a `$ref`'d schema or the inline schema by its pointer (`.../oneOf/0`)
+    pub fn tag_for(&self, index: usize, variant: &Schema) -> Option<&str> {
+        let Discriminator::Object { mapping, .. } = self else {
+            return None;
+        };
+        let inline_suffix = format!("/oneOf/{}", index);
+        mapping
+            .iter()
+            .find(|(_, target)| match variant {
+                Schema::Ref(schema_ref) => *target == &schema_ref.schema_path,
+                Schema::Def(_) => target.ends_with(&inline_suffix),
+            })
+            .map(|(tag, _)| tag.as_str())
+    }
+}
+
+/// A `not` schema, only the values it excludes through `const` or `enum` are supported
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Not {
+    #[serde(rename = "const")]
+    pub const_value: Option<serde_json::Value>,
+    #[serde(rename = "enum")]
+    #[serde(default)]
+    pub enum_values: Vec<serde_json::Value>,
+}
+
+/// Rewrites the draft-07 tuple form `items: [A, B]` of every schema in `value` to the
+/// `prefixItems: [A, B]` the deserializer reads, with `items: false` or the `additionalItems`
+/// schema for the items following them. Literal values (`const`, `enum`, `default` and the
+/// examples) are left untouched.
+pub fn normalize_tuple_items(value: &mut serde_yaml::Value) {
+    match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            if mapping
+                .get("items")
+                .is_some_and(serde_yaml::Value::is_sequence)
+            {
+                let prefix_items = mapping.remove("items").unwrap();
+                let tail = match mapping.remove("additionalItems") {
+                    Some(schema @ serde_yaml::Value::Mapping(_)) => schema,
+                    _ => false.into(),
+                };
+                mapping.insert("prefixItems".into(), prefix_items);
+                mapping.insert("items".into(), tail);
+            }
+            for (key, value) in mapping.iter_mut() {
+                let is_literal = key.as_str().is_some_and(|key| {
+                    ["const", "enum", "default", "example", "examples"].contains(&key)
+                });
+                if !is_literal {
+                    normalize_tuple_items(value);
+                }
+            }
+        }
+        serde_yaml::Value::Sequence(values) => values.iter_mut().for_each(normalize_tuple_items),
+        _ => {}
+    }
+}
+
+/// The `items` of a `prefixItems` tuple, `false` if the tuple has a fixed length, otherwise the
+/// schema of the items following the prefix
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum TupleItems {
+    Closed(MustBe!(false)),
+    Tail(Box<Schema>),
+}
+
+/// The vendor extensions of a schema (every `x-` key, e.g. `x-rust-type`), any other key the
+/// schema doesn't know is dropped
+#[derive(Debug, Default, Serialize, Clone)]
+#[serde(transparent)]
+pub struct Extensions(pub HashMap<String, serde_yaml::Value>);
+
+impl Extensions {
+    pub fn get(&self, key: &str) -> Option<&serde_yaml::Value> {
+        self.0.get(key)
+    }
+}
+
+impl<'de> Deserialize<'de> for Extensions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut entries = HashMap::<String, serde_yaml::Value>::deserialize(deserializer)?;
+        entries.retain(|key, _| key.starts_with("x-"));
+        Ok(Extensions(entries))
+    }
+}
+
+/// An integer literal of a schema (`const`, `enum`, `minimum`), anything from `i64::MIN` up to
+/// `u64::MAX`. `i128` itself can't be deserialized through the buffering of untagged enums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IntegerValue(pub i128);
+
+impl std::fmt::Display for IntegerValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'de> Deserialize<'de> for IntegerValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Integer {
+            Signed(i64),
+            Unsigned(u64),
+        }
+        Ok(IntegerValue(match Integer::deserialize(deserializer)? {
+            Integer::Signed(value) => value.into(),
+            Integer::Unsigned(value) => value.into(),
+        }))
+    }
+}
+
+impl Serialize for IntegerValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match i64::try_from(self.0) {
+            Ok(value) => serializer.serialize_i64(value),
+            Err(_) => serializer.serialize_u64(self.0 as u64),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(untagged)]
 /// A Schema can either be a $ref to another Schema or a Definition of a Schema.
 /// This deserializer assumes all top-level types are `SchemaDef`
+#[allow(clippy::large_enum_variant)]
 pub enum Schema {
     Ref(SchemaRef),
     Def(SchemaDef),
 }
 
+impl Schema {
+    /// The vendor extensions of a primitive, enum, array or object schema
+    pub fn extensions(&self) -> Option<&Extensions> {
+        match self {
+            Schema::Def(
+                SchemaDef::Object { extensions, .. }
+                | SchemaDef::Enum { extensions, .. }
+                | SchemaDef::String { extensions, .. }
+                | SchemaDef::Integer { extensions, .. }
+                | SchemaDef::Boolean { extensions, .. }
+                | SchemaDef::Number { extensions, .. }
+                | SchemaDef::Array { extensions, .. },
+            ) => Some(extensions),
+            _ => None,
+        }
+    }
+}
+
+/// A message definition in the `components -> messages` part of an asyncapi document, only the
+/// `payload` is relevant for code generation
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MessageDef {
+    pub payload: Option<Schema>,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use serde_yaml::Value;
+    use std::collections::HashMap;
 
     #[test]
     fn test_parse_complex_schema() {
         let content = include_str!("../resources/asyncapi.yaml");
-        let parsed_yaml = serde_yaml::from_str::<Value>(&content).unwrap();
+        let parsed_yaml = serde_yaml::from_str::<Value>(content).unwrap();
         let _parsed_schema = serde_yaml::from_value::<HashMap<String, SchemaDef>>(
             parsed_yaml["components"]["schemas"].clone(),
         )
@@ -229,4 +554,94 @@ mod test {
         "#;
         let _ = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
     }
+
+    #[test]
+    fn test_primitive_type_precedence() {
+        let yaml = r#"
+            WithFormat:
+                type: string
+                format: uuid
+                const: 67e55044-10b1-426f-9247-bb680e5fe0c8
+            ConstAndEnum:
+                type: string
+                const: a
+                enum: [a, b]
+            EnumAndFormat:
+                type: integer
+                format: int32
+                enum: [1, 2]
+            Basic:
+                type: integer
+                format: int32
+                minimum: 0
+        "#;
+        let parsed = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        assert!(matches!(
+            parsed.get("WithFormat"),
+            Some(SchemaDef::String {
+                type_def: PrimitiveType::Const { .. },
+                ..
+            })
+        ));
+        assert!(matches!(
+            parsed.get("ConstAndEnum"),
+            Some(SchemaDef::String {
+                type_def: PrimitiveType::Const { const_value },
+                ..
+            }) if const_value == "a"
+        ));
+        assert!(matches!(
+            parsed.get("EnumAndFormat"),
+            Some(SchemaDef::Integer {
+                type_def: PrimitiveType::Enum { enum_values },
+                ..
+            }) if enum_values.len() == 2
+        ));
+        assert!(matches!(
+            parsed.get("Basic"),
+            Some(SchemaDef::Integer {
+                type_def: PrimitiveType::Basic {
+                    format: Some(Format::Int32),
+                    minimum: Some(IntegerValue(0))
+                },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_primitive_type_mismatched_const() {
+        // used to silently become a plain string
+        let yaml = r#"
+            type: string
+            const: 5
+        "#;
+        assert!(serde_yaml::from_str::<SchemaDef>(yaml).is_err());
+    }
+
+    #[test]
+    fn test_parse_mixed_enum() {
+        let yaml = r#"
+            Status:
+                enum: ["active", 1, true]
+        "#;
+        let parsed = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        assert!(matches!(
+            parsed.get("Status"),
+            Some(SchemaDef::Enum { enum_values, .. }) if enum_values.len() == 3
+        ));
+    }
+
+    #[test]
+    fn test_schema_ref_name() {
+        let name = |path: &str| {
+            SchemaRef {
+                schema_path: path.to_string(),
+            }
+            .get_schema_name()
+        };
+        assert_eq!(name("#/components/schemas/Foo"), "Foo");
+        assert_eq!(name("#/definitions/Baz"), "Baz");
+        assert_eq!(name("#/components/schemas/Foo/properties/bar"), "FooBar");
+    }
 }
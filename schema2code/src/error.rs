@@ -0,0 +1,26 @@
+/// Errors that can occur while reading a specification and generating code from it
+#[derive(Debug, thiserror::Error)]
+pub enum GenerationError {
+    #[error("invalid specification: {0}")]
+    InvalidSpec(#[from] serde_yaml::Error),
+    #[error("invalid schema `{0}`: {1}")]
+    InvalidSchema(String, #[source] serde_yaml::Error),
+    #[error("nothing found at `{0}` in the specification")]
+    PointerNotFound(String),
+    #[error("`$ref` to `{0}` does not point to anything in the specification")]
+    UnresolvedRef(String),
+    #[error("`required` of `{0}` lists `{1}` which is not one of its properties")]
+    UnknownRequiredProperty(String, String),
+    #[error("`x-display` of `{0}` refers to `{1}` which is not one of its properties")]
+    UnknownDisplayProperty(String, String),
+    #[error("`{0}` is not supported: {1}")]
+    Unsupported(String, String),
+    #[error("`{0}` can't borrow its strings: {1}")]
+    UnsupportedBorrow(String, String),
+    #[error("failed to read `{0}`: {1}")]
+    Io(String, #[source] std::io::Error),
+    #[error("the schema `{0}` is defined more than once")]
+    DuplicateSchema(String),
+    #[error("failed to fetch `{0}`: {1}")]
+    RemoteRef(String, String),
+}
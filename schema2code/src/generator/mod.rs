@@ -1,9 +1,200 @@
 mod protobuf_gen;
 mod rust_gen;
-use crate::parser::Entity;
+mod typescript_gen;
+use crate::{parser::Entity, GenerationError};
 
-pub fn generate_rust(entities: Vec<Entity>) -> String {
-    rust_gen::generate_code(entities)
+/// Options that change the shape of the generated code, `Default` matches the behaviour of
+/// `generate_rust`
+#[derive(Debug, Clone)]
+pub struct GeneratorConfig {
+    /// Adds an `Unknown(String)` variant to string enums that captures any value not listed in
+    /// the schema instead of failing deserialization, for forward-compatible clients. A value
+    /// that would be named `Unknown` itself gets its index appended, e.g. `Unknown0`.
+    pub unknown_enum_variant: bool,
+    /// Emits `#[serde(deny_unknown_fields)]` on structs, structs that rely on `#[serde(flatten)]`
+    /// are skipped as serde doesn't support the combination
+    pub deny_unknown_fields: bool,
+    /// Enforces the validation keywords of the schema (e.g. `minItems`) while deserializing
+    pub validate: bool,
+    /// Omits optional fields that are `None` when serializing instead of writing `null`
+    pub skip_serializing_none: bool,
+    /// The visibility of generated items and their fields, e.g. `pub`, `pub(crate)` or `""` for
+    /// private
+    pub visibility: String,
+    /// Implements `PartialEq` and `Eq` by hand for structs with float fields, comparing the floats
+    /// by their bits so that equal values (including `NaN`) always compare equal. Structs with a
+    /// float nested in another type, like a `Vec<f64>`, only derive `PartialEq`
+    pub bitwise_float_eq: bool,
+    /// Appends an `Other(serde_json::Value)` variant to `OneOf` enums without a discriminator
+    /// that keeps any payload none of the variants match. Untagged enums try their variants in
+    /// order, so it's always the last one.
+    pub untagged_other_variant: bool,
+    /// Implements `Debug` by hand for structs with `writeOnly` fields, printing `"[redacted]"`
+    /// in place of their values
+    pub redact_write_only: bool,
+    /// Makes struct fields private and generates a getter (and a `_mut` getter) for each
+    pub encapsulate: bool,
+    /// Name of an untagged enum with a variant per message payload and a `from_json` helper to
+    /// dispatch incoming messages, only generated by `generate_rust_from_messages_with`
+    pub message_enum: Option<String>,
+    /// The map type objects with `additionalProperties` are generated as
+    pub map_type: MapType,
+    /// The type `string` fields are generated as
+    pub string_type: StringType,
+    /// Copies the fields of the structs an `allOf` combines into the composite struct instead of
+    /// embedding them with `#[serde(flatten)]`, which makes it compatible with
+    /// `deny_unknown_fields`. Falls back to `flatten` if a member isn't a struct or more than one
+    /// of them has `additionalProperties`.
+    pub inline_all_of: bool,
+    /// Leaves out the `serde` derives and attributes, `const` fields become plain fields that
+    /// are documented to always hold their value. Untyped values are still `serde_json::Value`
+    /// and `validate` has no effect as it hooks into deserialization.
+    pub no_serde: bool,
+    /// Generates types that also work with formats that aren't self-describing, like bincode or
+    /// postcard: `const` fields are plain fields documented to hold their value, `allOf`s are
+    /// inlined as with `inline_all_of`, `additionalProperties` are a regular field and optional
+    /// fields are always written. `oneOf`s and `enum`s of mixed values stay untagged (or
+    /// internally tagged) enums, which still need a self-describing format.
+    pub binary_compatible: bool,
+    /// Parse the schemas and generate the types on the rayon thread pool, off everything runs one
+    /// after the other on the current thread. The generated code is the same either way, only has
+    /// an effect with the `parallel` feature.
+    pub parallel: bool,
+    /// Fail with `GenerationError::UnknownRequiredProperty` when the `required` of an object
+    /// (message payloads included) lists a name that isn't in its `properties`, like
+    /// `ResolverConfig::strict_required`
+    pub strict_required: bool,
+    /// The path serde is reachable at from the generated code, for crates that only have it as a
+    /// re-export, e.g. `my_crate::serde`. Anything but `serde` also sets `#[serde(crate = ...)]`.
+    pub serde_path: String,
+    /// Derives `schemars::JsonSchema` (schemars 1.x) next to the serde traits. Renames and docs
+    /// are picked up from the serde attributes and doc comments, `const` and base64 fields are
+    /// described by `#[schemars(...)]` attributes as their types don't implement it. Needs the
+    /// `schemars` feature, generating fails without it.
+    pub derive_json_schema: bool,
+    /// Derives `sqlx::FromRow` (sqlx 0.8) on structs and `sqlx::Type` on enums of plain values,
+    /// which keep their values through `#[sqlx(rename = ...)]`. Fields of other entity types,
+    /// objects and tuples are read from JSON columns, `const` fields aren't read at all. Needs the
+    /// `sqlx` feature, generating fails without it.
+    pub derive_sqlx: bool,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            unknown_enum_variant: false,
+            deny_unknown_fields: false,
+            validate: false,
+            skip_serializing_none: true,
+            visibility: "pub".to_string(),
+            bitwise_float_eq: false,
+            untagged_other_variant: false,
+            redact_write_only: false,
+            encapsulate: false,
+            message_enum: None,
+            map_type: MapType::default(),
+            string_type: StringType::default(),
+            inline_all_of: false,
+            no_serde: false,
+            binary_compatible: false,
+            parallel: true,
+            strict_required: false,
+            serde_path: "serde".to_string(),
+            derive_json_schema: false,
+            derive_sqlx: false,
+        }
+    }
+}
+
+/// The Rust map types objects with `additionalProperties` can be generated as, `IndexMap` keeps
+/// the order of the keys and needs the `indexmap` crate with its `serde` feature
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MapType {
+    #[default]
+    HashMap,
+    BTreeMap,
+    IndexMap,
+}
+
+impl MapType {
+    pub(crate) fn path(self) -> &'static str {
+        match self {
+            MapType::HashMap => "std::collections::HashMap",
+            MapType::BTreeMap => "std::collections::BTreeMap",
+            MapType::IndexMap => "indexmap::IndexMap",
+        }
+    }
+}
+
+/// The Rust types strings can be generated as. `ArcStr` needs the `rc` feature of serde,
+/// `CompactString` the `compact_str` crate with its `serde` feature. `Cow` borrows from the input
+/// where it can, which adds a lifetime `'a` to the structs with string fields; it's limited to
+/// structs no other type refers to, generating anything else fails with
+/// `GenerationError::UnsupportedBorrow`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StringType {
+    #[default]
+    String,
+    BoxStr,
+    ArcStr,
+    CompactString,
+    Cow,
+}
+
+impl StringType {
+    pub(crate) fn path(self) -> &'static str {
+        match self {
+            StringType::String => "String",
+            StringType::BoxStr => "Box<str>",
+            StringType::ArcStr => "std::sync::Arc<str>",
+            StringType::CompactString => "compact_str::CompactString",
+            StringType::Cow => "std::borrow::Cow<'a, str>",
+        }
+    }
+}
+
+/// The languages code can be generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetLanguage {
+    Rust,
+    Protobuf,
+    TypeScript,
+}
+
+pub fn generate(entities: Vec<Entity>, target: TargetLanguage, config: &GeneratorConfig) -> String {
+    match target {
+        TargetLanguage::Rust => rust_gen::generate_code(entities, config),
+        TargetLanguage::Protobuf => protobuf_gen::generate_code(entities, config.parallel),
+        TargetLanguage::TypeScript => typescript_gen::generate_code(entities, config.parallel),
+    }
+}
+
+/// Like `generate` but first fails for entities the target can't generate valid code for, e.g. an
+/// `x-display` template naming a property that doesn't exist in Rust
+pub fn generate_checked(
+    entities: Vec<Entity>,
+    target: TargetLanguage,
+    config: &GeneratorConfig,
+) -> Result<String, GenerationError> {
+    if target == TargetLanguage::Rust {
+        check_rust(&entities, config)?;
+    }
+    Ok(generate(entities, target, config))
+}
+
+pub fn generate_rust(entities: Vec<Entity>, config: &GeneratorConfig) -> String {
+    generate(entities, TargetLanguage::Rust, config)
+}
+
+/// Fails for entities the Rust generator can't generate valid code for with `config`
+pub(crate) fn check_rust(
+    entities: &[Entity],
+    config: &GeneratorConfig,
+) -> Result<(), GenerationError> {
+    rust_gen::check_features(config)?;
+    rust_gen::check_borrowed_strings(entities, config)?;
+    rust_gen::check_validation_error(entities, config)?;
+    rust_gen::check_display_templates(entities)
 }
 
 pub(crate) fn snake_case(s: &str) -> String {
@@ -1,12 +1,7 @@
-use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use crate::{map_items, parser::Entity};
 
-use crate::parser::Entity;
-
-pub fn generate_code(entities: Vec<Entity>) -> String {
-    let code = entities
-        .into_par_iter()
-        .map(generate_entity)
-        .collect::<Vec<_>>();
+pub fn generate_code(entities: Vec<Entity>, parallel: bool) -> String {
+    let code = map_items(entities, parallel, generate_entity);
     code.join("\n")
 }
 
@@ -1,104 +1,1942 @@
 use super::snake_case;
-use proc_macro2::TokenStream;
-use quote::quote;
-use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use std::collections::{HashMap, HashSet};
 
-use crate::parser::{Entity, EntityDef, EnumDef, FieldType, Primitive, StructDef};
+use indexmap::IndexMap;
+use proc_macro2::{Delimiter, Group, Literal, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
 
-pub fn generate_code(entities: Vec<Entity>) -> String {
-    let code = entities
-        .into_par_iter()
-        .map(generate_entity)
+use super::{GeneratorConfig, StringType};
+use crate::{
+    map_items,
+    parser::{Constraint, Entity, EntityDef, EnumDef, Field, FieldType, Primitive, StructDef},
+    GenerationError,
+};
+
+pub fn generate_code(entities: Vec<Entity>, config: &GeneratorConfig) -> String {
+    // every field has to be in its place, a non-self-describing format can't skip or flatten
+    let binary_config;
+    let config = if config.binary_compatible {
+        binary_config = GeneratorConfig {
+            inline_all_of: true,
+            skip_serializing_none: false,
+            ..config.clone()
+        };
+        &binary_config
+    } else {
+        config
+    };
+    let entities = strip_discriminator_fields(entities);
+    let needs_validation_error = has_validate(&entities, config);
+    let examples = generate_examples(&entities, config);
+    let borrowing = borrowing_entities(&entities, config);
+    let known = KnownEntities {
+        sqlx_types: sqlx_types(&entities, config),
+        partial_eq_only: partial_eq_only(&entities, config),
+    };
+    let inlined = if config.inline_all_of {
+        inline_all_of(&entities)
+    } else {
+        HashMap::new()
+    };
+    let mut code = map_items(entities, config.parallel, |entity| {
+        let entity_name = entity.name.clone();
+        let code = match inlined.get(&entity.name) {
+            Some((def, conversions)) => {
+                let name = entity.name.clone();
+                let def = EntityDef::Struct(def.clone());
+                format!(
+                    "{}\n{}",
+                    generate_entity(Entity { name, def }, config, &known),
+                    conversions
+                )
+            }
+            None => generate_entity(entity, config, &known),
+        };
+        let code = match examples.get(&entity_name) {
+            Some(example) => format!("{}\n{}", example, code),
+            None => code,
+        };
+        if borrowing.contains(&entity_name) {
+            with_lifetime(code.parse().unwrap(), &entity_name).to_string()
+        } else {
+            code
+        }
+    })
+    .join("\n");
+    if needs_validation_error {
+        code = format!("{}\n{}", code, validation_error(config));
+    }
+    #[cfg(feature = "base64")]
+    if code.contains("\"base64_bytes") {
+        return format!("{}\n{}", code, base64_helper(config));
+    }
+    code
+}
+
+/// Whether any struct gets a `validate()`, which needs the shared `ValidationError`
+fn has_validate(entities: &[Entity], config: &GeneratorConfig) -> bool {
+    config.validate
+        && !config.no_serde
+        && entities.iter().any(|entity| match &entity.def {
+            EntityDef::Struct(StructDef {
+                properties,
+                dependent_required,
+                ..
+            }) => {
+                !dependent_required.is_empty()
+                    || properties
+                        .values()
+                        .any(|field| !field.constraints.is_empty())
+            }
+            _ => false,
+        })
+}
+
+/// Fails for the options of `config` that need a feature this build doesn't have
+pub(crate) fn check_features(config: &GeneratorConfig) -> Result<(), GenerationError> {
+    if config.derive_json_schema && !cfg!(feature = "schemars") {
+        return Err(GenerationError::Unsupported(
+            "derive_json_schema".to_string(),
+            "the `schemars` feature is not enabled".to_string(),
+        ));
+    }
+    if config.derive_sqlx && !cfg!(feature = "sqlx") {
+        return Err(GenerationError::Unsupported(
+            "derive_sqlx".to_string(),
+            "the `sqlx` feature is not enabled".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The shared `ValidationError` can't be generated next to an entity of the same name
+pub(crate) fn check_validation_error(
+    entities: &[Entity],
+    config: &GeneratorConfig,
+) -> Result<(), GenerationError> {
+    if has_validate(entities, config)
+        && entities
+            .iter()
+            .any(|entity| entity.name == "ValidationError")
+    {
+        return Err(GenerationError::Unsupported(
+            "ValidationError".to_string(),
+            "`validate` generates a type of that name for the errors of `validate()`".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The error `validate()` returns, shared by every generated struct
+fn validation_error(config: &GeneratorConfig) -> String {
+    let vis: TokenStream = config.visibility.parse().unwrap();
+    let error = quote! {
+        /// A value that breaks a constraint of its schema, found by `validate()`
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct ValidationError {
+            /// The property of the schema whose constraint is broken
+            pub field: String,
+            pub message: String,
+        }
+
+        impl std::fmt::Display for ValidationError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.message)
+            }
+        }
+
+        impl std::error::Error for ValidationError {}
+    };
+    error.to_string()
+}
+
+/// Whether a field holds a string, which borrows from the input with `StringType::Cow`
+fn borrows_str(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::Simple(Primitive::String) => true,
+        FieldType::Array(Some(inner))
+        | FieldType::Object(Some(inner))
+        | FieldType::Boxed(inner) => borrows_str(inner),
+        FieldType::Tuple(tuple_types) => tuple_types.iter().any(borrows_str),
+        _ => false,
+    }
+}
+
+/// `borrows_str` for a struct field, unless `x-rust-type` replaces its type
+fn field_borrows_str(field: &Field) -> bool {
+    field.extensions.get("x-rust-type").is_none() && borrows_str(&field.field_type)
+}
+
+/// The entities that get a lifetime parameter for their `Cow<'a, str>`s
+fn borrowing_entities(entities: &[Entity], config: &GeneratorConfig) -> HashSet<String> {
+    if config.string_type != StringType::Cow {
+        return HashSet::new();
+    }
+    entities
+        .iter()
+        .filter(|entity| match &entity.def {
+            EntityDef::Struct(StructDef {
+                properties,
+                additional_properties,
+                ..
+            }) => {
+                properties.values().any(field_borrows_str)
+                    || additional_properties.as_ref().is_some_and(borrows_str)
+            }
+            EntityDef::Tuple(members, tail) => members.iter().chain(tail).any(borrows_str),
+            EntityDef::MixedEnum(members) => members.iter().any(borrows_str),
+            EntityDef::Map(Some(value_type), _) => borrows_str(value_type),
+            _ => false,
+        })
+        .map(|entity| entity.name.clone())
+        .collect()
+}
+
+/// The names of the entities a definition refers to
+fn referenced_names(def: &EntityDef) -> Vec<&str> {
+    fn named<'a>(field_type: &'a FieldType, names: &mut Vec<&'a str>) {
+        match field_type {
+            FieldType::Named(name) => names.push(name),
+            FieldType::Array(Some(inner))
+            | FieldType::Object(Some(inner))
+            | FieldType::Boxed(inner) => named(inner, names),
+            FieldType::Tuple(tuple_types) => {
+                tuple_types.iter().for_each(|inner| named(inner, names))
+            }
+            _ => {}
+        }
+    }
+    let mut names = vec![];
+    match def {
+        EntityDef::Struct(StructDef {
+            properties,
+            additional_properties,
+            ..
+        }) => {
+            properties
+                .values()
+                .for_each(|field| named(&field.field_type, &mut names));
+            if let Some(additional_properties) = additional_properties {
+                named(additional_properties, &mut names);
+            }
+        }
+        EntityDef::OneOf { variants: all, .. } | EntityDef::AllOf(all) => {
+            names.extend(all.iter().map(String::as_str))
+        }
+        EntityDef::Tuple(members, tail) => members
+            .iter()
+            .chain(tail)
+            .for_each(|member| named(member, &mut names)),
+        EntityDef::MixedEnum(members) => {
+            members.iter().for_each(|member| named(member, &mut names))
+        }
+        EntityDef::Map(Some(value_type), _) => named(value_type, &mut names),
+        EntityDef::Alias(target) => named(target, &mut names),
+        EntityDef::Map(None, _) | EntityDef::Enum(_) => {}
+    }
+    names
+}
+
+/// With `StringType::Cow` only structs get a lifetime and nothing may refer to them, as the
+/// lifetime would have to be added to every type on the way
+pub(crate) fn check_borrowed_strings(
+    entities: &[Entity],
+    config: &GeneratorConfig,
+) -> Result<(), GenerationError> {
+    let borrowing = borrowing_entities(entities, config);
+    for entity in entities {
+        if borrowing.contains(&entity.name) {
+            let reason = match &entity.def {
+                EntityDef::Struct(def) if config.validate && !def.dependent_required.is_empty() => {
+                    Some("`validate` deserializes structs with `dependentRequired` through a twin struct")
+                }
+                EntityDef::Struct(_) => None,
+                _ => Some("only structs can take a lifetime"),
+            };
+            if let Some(reason) = reason {
+                return Err(GenerationError::UnsupportedBorrow(
+                    entity.name.clone(),
+                    reason.to_string(),
+                ));
+            }
+        }
+        if let Some(name) = referenced_names(&entity.def)
+            .into_iter()
+            .find(|name| borrowing.contains(*name))
+        {
+            return Err(GenerationError::UnsupportedBorrow(
+                name.to_string(),
+                format!(
+                    "`{}` refers to it and would need a lifetime too",
+                    entity.name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Splits an `x-display` template into a `format!` string and the properties it interpolates, in
+/// order. `{{` and `}}` stand for literal braces, like in `format!`
+fn parse_display_template(template: &str) -> (String, Vec<String>) {
+    let mut format = String::new();
+    let mut properties = vec![];
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '}']) {
+        format.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            format.push_str(&rest[..2]);
+            rest = &rest[2..];
+        } else if let Some((property, after)) =
+            rest.strip_prefix('{').and_then(|rest| rest.split_once('}'))
+        {
+            format.push_str("{}");
+            properties.push(property.to_string());
+            rest = after;
+        } else {
+            // a brace without its pair is kept as it is
+            format.push_str(if rest.starts_with('{') { "{{" } else { "}}" });
+            rest = &rest[1..];
+        }
+    }
+    format.push_str(rest);
+    (format, properties)
+}
+
+/// Every property an `x-display` template interpolates has to exist on the struct
+pub(crate) fn check_display_templates(entities: &[Entity]) -> Result<(), GenerationError> {
+    for entity in entities {
+        if let EntityDef::Struct(StructDef {
+            properties,
+            display: Some(template),
+            ..
+        }) = &entity.def
+        {
+            let (_, referenced) = parse_display_template(template);
+            if let Some(property) = referenced
+                .into_iter()
+                .find(|property| !properties.contains_key(property))
+            {
+                return Err(GenerationError::UnknownDisplayProperty(
+                    entity.name.clone(),
+                    property,
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `impl Display` from an `x-display` template, an optional property that isn't set is left out
+fn generate_display(
+    identifier: &TokenStream,
+    template: &str,
+    properties: &IndexMap<String, Field>,
+    rust_names: &HashMap<String, String>,
+) -> TokenStream {
+    let (format, referenced) = parse_display_template(template);
+    let args = referenced.iter().map(|property| {
+        let field_name: TokenStream = rust_names[property].parse().unwrap();
+        if properties[property].optional {
+            quote!(self.#field_name.as_ref().map(ToString::to_string).unwrap_or_default())
+        } else {
+            quote!(self.#field_name)
+        }
+    });
+    quote! {
+        impl std::fmt::Display for #identifier {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, #format, #(#args),*)
+            }
+        }
+    }
+}
+
+/// Adds the lifetime `'a` to the declaration of the struct `name` and to the impls for it
+fn with_lifetime(code: TokenStream, name: &str) -> TokenStream {
+    let mut rewritten: Vec<TokenTree> = vec![];
+    let mut tokens = code.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident)
+                if ident == "impl"
+                    && !matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '<') =>
+            {
+                rewritten.push(TokenTree::Ident(ident));
+                rewritten.extend(quote!(<'a>));
+            }
+            TokenTree::Ident(ident) if ident == name => {
+                // `Name { .. }` is a struct expression, unless it follows `struct`, `for` or the
+                // `impl<'a>` added above
+                let is_declaration = match rewritten.last() {
+                    Some(TokenTree::Ident(i)) => i == "struct" || i == "for",
+                    Some(TokenTree::Punct(p)) => p.as_char() == '>',
+                    _ => false,
+                };
+                let is_expression = !is_declaration
+                    && matches!(tokens.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace);
+                let has_generics =
+                    matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '<');
+                rewritten.push(TokenTree::Ident(ident));
+                if !is_expression && !has_generics {
+                    rewritten.extend(quote!(<'a>));
+                }
+            }
+            TokenTree::Group(group) => {
+                let mut inner = Group::new(group.delimiter(), with_lifetime(group.stream(), name));
+                inner.set_span(group.span());
+                rewritten.push(TokenTree::Group(inner));
+            }
+            token => rewritten.push(token),
+        }
+    }
+    rewritten.into_iter().collect()
+}
+
+/// Removes the discriminator property from the variant structs of internally tagged `OneOf`s.
+/// Serde consumes the tag before it deserializes the variant, so a variant that declares the tag
+/// itself (usually as a `const`) would never find it. The variants are changed wherever they're used.
+fn strip_discriminator_fields(mut entities: Vec<Entity>) -> Vec<Entity> {
+    let tagged_variants = entities
+        .iter()
+        .filter_map(|entity| match &entity.def {
+            EntityDef::OneOf {
+                discriminant: Some(discriminant),
+                variants,
+                ..
+            } => Some(
+                variants
+                    .iter()
+                    .map(move |variant| (variant.clone(), discriminant.clone())),
+            ),
+            _ => None,
+        })
+        .flatten()
         .collect::<Vec<_>>();
-    code.join("\n")
+    for entity in &mut entities {
+        if let EntityDef::Struct(struct_def) = &mut entity.def {
+            for (_, discriminant) in tagged_variants
+                .iter()
+                .filter(|(variant, _)| *variant == entity.name)
+            {
+                struct_def.properties.shift_remove(discriminant);
+            }
+        }
+    }
+    entities
+}
+
+/// The Rust field names of a struct's properties by property name, properties whose names
+/// collide after snake_casing, e.g. `userId` and `user_id`, get a number appended from the
+/// second one on
+fn rust_field_names(properties: &IndexMap<String, Field>) -> HashMap<String, String> {
+    let mut taken = HashSet::new();
+    properties
+        .keys()
+        .map(|name| {
+            let field_name = snake_case(name);
+            let field_name = (1..)
+                .map(|count| match count {
+                    1 => field_name.clone(),
+                    _ => format!("{}_{}", field_name, count),
+                })
+                .find(|candidate| !taken.contains(candidate))
+                .unwrap();
+            taken.insert(field_name.clone());
+            (name.clone(), field_name)
+        })
+        .collect()
+}
+
+/// The `allOf` entities whose members can all be inlined, as the struct combining their fields and
+/// the `From<&Composite>` conversions into the members
+fn inline_all_of(entities: &[Entity]) -> HashMap<String, (StructDef, String)> {
+    let defs = entities
+        .iter()
+        .map(|entity| (entity.name.as_str(), &entity.def))
+        .collect::<HashMap<_, _>>();
+    entities
+        .iter()
+        .filter_map(|entity| match &entity.def {
+            EntityDef::AllOf(all_of) => {
+                let composite = inlined_struct(all_of, &defs)?;
+                let identifier: TokenStream = entity.name.parse().unwrap();
+                let composite_names = rust_field_names(&composite.properties);
+                let conversions = all_of.iter().map(|member| {
+                    let member_def = inlined_struct(std::slice::from_ref(member), &defs).unwrap();
+                    let member_type: TokenStream = member.parse().unwrap();
+                    let member_names = rust_field_names(&member_def.properties);
+                    let (mut fields, mut values): (Vec<TokenStream>, Vec<TokenStream>) = member_def
+                        .properties
+                        .keys()
+                        .map(|name| {
+                            (
+                                member_names[name].parse().unwrap(),
+                                composite_names[name].parse().unwrap(),
+                            )
+                        })
+                        .unzip();
+                    if member_def.additional_properties.is_some() {
+                        fields.push(quote!(additional_properties));
+                        values.push(quote!(additional_properties));
+                    }
+                    quote! {
+                        impl From<&#identifier> for #member_type {
+                            fn from(value: &#identifier) -> Self {
+                                Self {
+                                    #(#fields: value.#values.clone()),*
+                                }
+                            }
+                        }
+                    }
+                });
+                Some((
+                    entity.name.clone(),
+                    (composite, quote!(#(#conversions)*).to_string()),
+                ))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Combines the fields of the structs (or `allOf`s of structs) named in `all_of`, a property
+/// declared by several members takes the definition of the last one
+fn inlined_struct(all_of: &[String], defs: &HashMap<&str, &EntityDef>) -> Option<StructDef> {
+    let mut composite = StructDef {
+        properties: Default::default(),
+        additional_properties: None,
+        example: None,
+        dependent_required: Default::default(),
+        display: None,
+    };
+    for member in all_of {
+        let member = match defs.get(member.as_str())? {
+            EntityDef::Struct(member) => member.clone(),
+            EntityDef::AllOf(members) => inlined_struct(members, defs)?,
+            _ => return None,
+        };
+        if member.additional_properties.is_some() {
+            // the members' maps can't be told apart anymore
+            if composite.additional_properties.is_some() {
+                return None;
+            }
+            composite.additional_properties = member.additional_properties;
+        }
+        composite.properties.extend(member.properties);
+        composite
+            .dependent_required
+            .extend(member.dependent_required);
+    }
+    Some(composite)
+}
+
+/// The module that `format: byte` fields are (de)serialized `with`, `Vec<u8>` would otherwise be
+/// a JSON array of numbers instead of a base64 string
+#[cfg(feature = "base64")]
+fn base64_helper(config: &GeneratorConfig) -> String {
+    let helper = quote! {
+        mod base64_bytes {
+            use base64::Engine;
+
+            pub fn serialize<S: serde::Serializer>(
+                bytes: &[u8],
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(bytes))
+            }
+
+            pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Vec<u8>, D::Error> {
+                let encoded = <String as serde::Deserialize>::deserialize(deserializer)?;
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded)
+                    .map_err(serde::de::Error::custom)
+            }
+
+            pub mod option {
+                pub fn serialize<S: serde::Serializer>(
+                    bytes: &Option<Vec<u8>>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    match bytes {
+                        Some(bytes) => super::serialize(bytes, serializer),
+                        None => serializer.serialize_none(),
+                    }
+                }
+
+                pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Option<Vec<u8>>, D::Error> {
+                    #[derive(serde::Deserialize)]
+                    struct Bytes(#[serde(deserialize_with = "super::deserialize")] Vec<u8>);
+                    let bytes = <Option<Bytes> as serde::Deserialize>::deserialize(deserializer)?;
+                    Ok(bytes.map(|Bytes(bytes)| bytes))
+                }
+            }
+        }
+    };
+    with_serde_path(helper, config).to_string()
+}
+
+/// Generates `impl Name { pub fn example() -> Self }` for every struct with an example in its
+/// schema, the example needs the definitions of the other entities to build nested values.
+/// Examples that don't fit the generated types are skipped.
+fn generate_examples(entities: &[Entity], config: &GeneratorConfig) -> HashMap<String, String> {
+    let definitions = entities
+        .iter()
+        .map(|entity| (entity.name.as_str(), &entity.def))
+        .collect::<HashMap<_, _>>();
+    entities
+        .iter()
+        .filter_map(|entity| {
+            let EntityDef::Struct(
+                struct_def @ StructDef {
+                    example: Some(example),
+                    ..
+                },
+            ) = &entity.def
+            else {
+                return None;
+            };
+            let value = example_struct(&entity.name, struct_def, example, &definitions, config)?;
+            let identifier: TokenStream = entity.name.parse().unwrap();
+            let code = quote! {
+                impl #identifier {
+                    /// The example value given in the schema
+                    pub fn example() -> Self {
+                        #value
+                    }
+                }
+            };
+            Some((entity.name.clone(), code.to_string()))
+        })
+        .collect()
 }
 
-fn expand_field_type(field_type: FieldType) -> String {
+fn example_struct(
+    name: &str,
+    struct_def: &StructDef,
+    value: &serde_json::Value,
+    definitions: &HashMap<&str, &EntityDef>,
+    config: &GeneratorConfig,
+) -> Option<TokenStream> {
+    let values = value.as_object()?;
+    if values
+        .keys()
+        .any(|key| !struct_def.properties.contains_key(key))
+    {
+        return None;
+    }
+    let names = rust_field_names(&struct_def.properties);
+    let mut fields = struct_def
+        .properties
+        .iter()
+        .map(|(field_name, field)| {
+            let value = match values.get(field_name) {
+                Some(value) => {
+                    let value = example_value(&field.field_type, value, definitions, config)?;
+                    if field.optional {
+                        quote!(Some(#value))
+                    } else {
+                        value
+                    }
+                }
+                None if field.optional => quote!(None),
+                None => return None,
+            };
+            let field_name: TokenStream = names[field_name].parse().unwrap();
+            Some(quote!(#field_name: #value))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if struct_def.additional_properties.is_some() {
+        fields.push(quote!(additional_properties: Default::default()));
+    }
+    let identifier: TokenStream = name.parse().unwrap();
+    Some(quote! {
+        #identifier {
+            #(#fields),*
+        }
+    })
+}
+
+/// Turns a JSON value into an expression of the type `field_type` generates to
+fn example_value(
+    field_type: &FieldType,
+    value: &serde_json::Value,
+    definitions: &HashMap<&str, &EntityDef>,
+    config: &GeneratorConfig,
+) -> Option<TokenStream> {
+    let value = match field_type {
+        FieldType::Simple(Primitive::String) => {
+            let value = value.as_str()?;
+            if config.string_type == StringType::String {
+                quote!(#value.to_string())
+            } else {
+                quote!(#value.into())
+            }
+        }
+        FieldType::Simple(Primitive::Bool) => {
+            let value = value.as_bool()?;
+            quote!(#value)
+        }
+        FieldType::Simple(Primitive::I8) => {
+            Literal::i8_suffixed(value.as_i64()?.try_into().ok()?).into_token_stream()
+        }
+        FieldType::Simple(Primitive::I16) => {
+            Literal::i16_suffixed(value.as_i64()?.try_into().ok()?).into_token_stream()
+        }
+        FieldType::Simple(Primitive::Int) => {
+            Literal::i32_suffixed(value.as_i64()?.try_into().ok()?).into_token_stream()
+        }
+        FieldType::Simple(Primitive::Long) => {
+            Literal::i64_suffixed(value.as_i64()?).into_token_stream()
+        }
+        FieldType::Simple(Primitive::I128) => {
+            Literal::i128_suffixed(value.as_i64()?.into()).into_token_stream()
+        }
+        FieldType::Simple(Primitive::U8) => {
+            Literal::u8_suffixed(value.as_u64()?.try_into().ok()?).into_token_stream()
+        }
+        FieldType::Simple(Primitive::U16) => {
+            Literal::u16_suffixed(value.as_u64()?.try_into().ok()?).into_token_stream()
+        }
+        FieldType::Simple(Primitive::U32) => {
+            Literal::u32_suffixed(value.as_u64()?.try_into().ok()?).into_token_stream()
+        }
+        FieldType::Simple(Primitive::U64) => {
+            Literal::u64_suffixed(value.as_u64()?).into_token_stream()
+        }
+        FieldType::Simple(Primitive::U128) => {
+            Literal::u128_suffixed(value.as_u64()?.into()).into_token_stream()
+        }
+        FieldType::Simple(Primitive::Float) => {
+            Literal::f32_suffixed(value.as_f64()? as f32).into_token_stream()
+        }
+        FieldType::Simple(Primitive::Double) => {
+            Literal::f64_suffixed(value.as_f64()?).into_token_stream()
+        }
+        // a const only has a single value, whether the example matches is up to the schema
+        FieldType::Const(primitive, value) if plain_consts(config) => const_value(primitive, value),
+        FieldType::Const(..) => quote!(Default::default()),
+        FieldType::Array(Some(item_type)) => {
+            let items = value
+                .as_array()?
+                .iter()
+                .map(|item| example_value(item_type, item, definitions, config))
+                .collect::<Option<Vec<_>>>()?;
+            quote!(vec![#(#items),*])
+        }
+        FieldType::Boxed(inner) => {
+            let value = example_value(inner, value, definitions, config)?;
+            quote!(Box::new(#value))
+        }
+        FieldType::Named(name) => match definitions.get(name.as_str())? {
+            EntityDef::Struct(struct_def) => {
+                example_struct(name, struct_def, value, definitions, config)?
+            }
+            EntityDef::Enum(enum_def) => {
+                let value = match value {
+                    serde_json::Value::Number(number) if enum_def.repr.is_some() => {
+                        number.to_string()
+                    }
+                    _ => value.as_str()?.to_string(),
+                };
+                let identifier: TokenStream = name.parse().unwrap();
+                let index = enum_def
+                    .values
+                    .iter()
+                    .position(|variant| *variant == value)?;
+                let variant = enum_variant_names(enum_def, config).swap_remove(index);
+                quote!(#identifier::#variant)
+            }
+            EntityDef::Alias(target) => example_value(target, value, definitions, config)?,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Integer constants need a typed literal for `MustBe!` to be serializable, JSON deserializers
+/// hand out non-negative integers as `u64` and negative ones as `i64` so the type follows the sign.
+fn integer_const(value: &str) -> String {
+    if value.starts_with('-') {
+        format!("monostate::MustBe!({}i64)", value)
+    } else {
+        format!("monostate::MustBe!({}u64)", value)
+    }
+}
+
+fn expand_field_type(field_type: FieldType, config: &GeneratorConfig) -> String {
     match field_type {
         FieldType::Named(t) => t,
-        FieldType::Array(Some(item_type)) => format!("Vec<{}>", expand_field_type(*item_type)),
+        FieldType::Array(Some(item_type)) => {
+            format!("Vec<{}>", expand_field_type(*item_type, config))
+        }
         FieldType::Array(None) => "Vec<serde_json::Value>".into(),
         FieldType::Object(Some(value_type)) => {
             format!(
-                "std::collections::HashMap<String, {}>",
-                expand_field_type(*value_type)
+                "{}<String, {}>",
+                config.map_type.path(),
+                expand_field_type(*value_type, config)
             )
         }
         FieldType::Object(None) => "serde_json::Value".into(),
         FieldType::Tuple(tuple_types) => {
             let tuple_types = tuple_types
                 .into_iter()
-                .map(expand_field_type)
+                .map(|tuple_type| expand_field_type(tuple_type, config))
                 .collect::<Vec<_>>();
             format!("({})", tuple_types.join(", "))
         }
+        FieldType::Boxed(inner) => format!("Box<{}>", expand_field_type(*inner, config)),
         FieldType::Simple(primitive) => match primitive {
-            Primitive::String => "String".into(),
+            Primitive::String => config.string_type.path().into(),
+            Primitive::I8 => "i8".into(),
+            Primitive::I16 => "i16".into(),
             Primitive::Int => "i32".into(),
+            Primitive::I128 => "i128".into(),
+            Primitive::U8 => "u8".into(),
+            Primitive::U16 => "u16".into(),
+            Primitive::U128 => "u128".into(),
             Primitive::Double => "f64".into(),
             Primitive::Bool => "bool".into(),
             Primitive::Long => "i64".into(),
             Primitive::Float => "f32".into(),
             Primitive::Uuid => "uuid::Uuid".into(),
             Primitive::Bytes => "Vec<u8>".into(),
+            Primitive::Decimal => "rust_decimal::Decimal".into(),
             Primitive::U32 => "u32".into(),
             Primitive::U64 => "u64".into(),
         },
+        FieldType::Const(primitive, value) if plain_consts(config) => {
+            const_type(&primitive, &value).into()
+        }
         FieldType::Const(primitive, value) => match primitive {
-            Primitive::String => format!("monostate::MustBe!(\"{}\")", value),
-            Primitive::Int => format!("monostate::MustBe!({})", value),
+            // escaped like any Rust string literal, the value may contain quotes or backslashes,
+            // decimals are serialized as strings to keep their precision
+            Primitive::String | Primitive::Decimal => {
+                format!("monostate::MustBe!({})", Literal::string(&value))
+            }
+            Primitive::I8
+            | Primitive::I16
+            | Primitive::Int
+            | Primitive::Long
+            | Primitive::I128
+            | Primitive::U8
+            | Primitive::U16
+            | Primitive::U32
+            | Primitive::U64
+            | Primitive::U128 => integer_const(&value),
             Primitive::Double => format!("monostate::MustBe!({})", value),
             Primitive::Bool => format!("monostate::MustBe!({})", value),
-            Primitive::Long => format!("monostate::MustBe!({})", value),
             Primitive::Float => format!("monostate::MustBe!({})", value),
-            Primitive::U32 => format!("monostate::MustBe!({})", value),
-            Primitive::U64 => format!("monostate::MustBe!({})", value),
             Primitive::Uuid => todo!(),
             Primitive::Bytes => todo!(),
         },
     }
 }
 
-fn generate_entity(entity: Entity) -> String {
+/// The plain type a const is generated as instead of `MustBe!`, see `plain_consts`
+fn const_type(primitive: &Primitive, value: &str) -> &'static str {
+    match primitive {
+        Primitive::String | Primitive::Decimal => "String",
+        Primitive::Bool => "bool",
+        Primitive::Float => "f32",
+        Primitive::Double => "f64",
+        _ if value.starts_with('-') => "i64",
+        _ => "u64",
+    }
+}
+
+/// The value of a const as an expression of the plain type it is generated as, see `const_type`
+fn const_value(primitive: &Primitive, value: &str) -> TokenStream {
+    match primitive {
+        Primitive::String | Primitive::Decimal => quote!(#value.to_string()),
+        Primitive::Bool | Primitive::Float | Primitive::Double => value.parse().unwrap(),
+        _ if value.starts_with('-') => format!("{}i64", value).parse().unwrap(),
+        _ => format!("{}u64", value).parse().unwrap(),
+    }
+}
+
+/// `value()` and `TryFrom<i64>` for a `MixedEnum` whose values are all integer constants that fit
+/// an `i64`, e.g. `enum: [1, 2, 5]`
+fn integer_enum_conversions(
+    name: &str,
+    values: &[FieldType],
+    config: &GeneratorConfig,
+) -> Option<TokenStream> {
+    let variants = literal_variant_names(values, &[]);
+    let mut integers = vec![];
+    let mut constructors = vec![];
+    for value in values {
+        let FieldType::Const(primitive, literal) = value else {
+            return None;
+        };
+        if matches!(
+            primitive,
+            Primitive::String
+                | Primitive::Decimal
+                | Primitive::Bool
+                | Primitive::Float
+                | Primitive::Double
+        ) {
+            return None;
+        }
+        integers.push(Literal::i64_unsuffixed(literal.parse().ok()?));
+        constructors.push(if config.no_serde {
+            const_value(primitive, literal)
+        } else {
+            quote!(Default::default())
+        });
+    }
+    let identifier: TokenStream = name.parse().unwrap();
+    let error = format!("unknown {} value: {{}}", name);
+    Some(quote! {
+        impl #identifier {
+            /// The integer this variant stands for
+            pub fn value(&self) -> i64 {
+                match self {
+                    #(Self::#variants(_) => #integers),*
+                }
+            }
+        }
+
+        impl TryFrom<i64> for #identifier {
+            type Error = String;
+
+            fn try_from(value: i64) -> Result<Self, Self::Error> {
+                match value {
+                    #(#integers => Ok(Self::#variants(#constructors)),)*
+                    _ => Err(format!(#error, value)),
+                }
+            }
+        }
+    })
+}
+
+/// A tuple with a variadic tail, the members followed by a `Vec` of the tail. The derives would
+/// (de)serialize the `Vec` as a nested array, so the impls put its items in the same array
+fn generate_variadic_tuple(
+    name: &str,
+    members: Vec<FieldType>,
+    tail: FieldType,
+    eq: Option<TokenStream>,
+    config: &GeneratorConfig,
+) -> TokenStream {
+    let vis: TokenStream = config.visibility.parse().unwrap();
+    let identifier: TokenStream = name.parse().unwrap();
+    let member_types = members
+        .into_iter()
+        .map(|member| expand_field_type(member, config).parse().unwrap())
+        .collect::<Vec<TokenStream>>();
+    let tail: TokenStream = expand_field_type(tail, config).parse().unwrap();
+    let indices = (0..member_types.len())
+        .map(Literal::usize_unsuffixed)
+        .collect::<Vec<_>>();
+    let tail_index = Literal::usize_unsuffixed(member_types.len());
+    let items = (0..member_types.len())
+        .map(|index| format_ident!("item{}", index))
+        .collect::<Vec<_>>();
+    let min_items = member_types.len();
+    let expecting = format!("an array of at least {} items", min_items);
+    let serde_impls = (!config.no_serde).then(|| {
+        quote! {
+            impl serde::Serialize for #identifier {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    use serde::ser::SerializeSeq;
+                    let mut seq = serializer.serialize_seq(Some(#min_items + self.#tail_index.len()))?;
+                    #(seq.serialize_element(&self.#indices)?;)*
+                    for item in &self.#tail_index {
+                        seq.serialize_element(item)?;
+                    }
+                    seq.end()
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for #identifier {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    struct Visitor;
+
+                    impl<'de> serde::de::Visitor<'de> for Visitor {
+                        type Value = #identifier;
+
+                        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                            formatter.write_str(#expecting)
+                        }
+
+                        fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                            #(
+                                let #items = seq
+                                    .next_element()?
+                                    .ok_or_else(|| serde::de::Error::invalid_length(#indices, &self))?;
+                            )*
+                            let mut tail = Vec::new();
+                            while let Some(item) = seq.next_element()? {
+                                tail.push(item);
+                            }
+                            Ok(#identifier(#(#items,)* tail))
+                        }
+                    }
+
+                    deserializer.deserialize_seq(Visitor)
+                }
+            }
+        }
+    });
+    let json_schema = derives_json_schema(config).then(|| {
+        quote! {
+            impl schemars::JsonSchema for #identifier {
+                fn schema_name() -> std::borrow::Cow<'static, str> {
+                    #name.into()
+                }
+
+                fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                    schemars::json_schema!({
+                        "type": "array",
+                        "prefixItems": [#(generator.subschema_for::<#member_types>()),*],
+                        "items": generator.subschema_for::<#tail>(),
+                        "minItems": #min_items,
+                    })
+                }
+            }
+        }
+    });
+    quote! {
+        #[derive(Debug, Clone, #eq PartialEq)]
+        #vis struct #identifier(#(#vis #member_types,)* #vis Vec<#tail>);
+
+        #serde_impls
+        #json_schema
+    }
+}
+
+/// Points the `serde::...` paths of generated code to `config.serde_path` and tells the serde
+/// derives where to find it with `#[serde(crate = ...)]`
+fn with_serde_path(code: TokenStream, config: &GeneratorConfig) -> TokenStream {
+    if config.serde_path == "serde" {
+        return code;
+    }
+    let path: TokenStream = config.serde_path.parse().unwrap();
+    let serde_path = config.serde_path.as_str();
+    let mut rewritten = vec![];
+    let mut tokens = code.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident)
+                if ident == "serde"
+                    && matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ':') =>
+            {
+                rewritten.extend(path.clone());
+            }
+            TokenTree::Group(group) => {
+                let is_serde_derive = group.delimiter() == Delimiter::Bracket
+                    && matches!(rewritten.last(), Some(TokenTree::Punct(p)) if p.as_char() == '#')
+                    && matches!(group.stream().into_iter().next(), Some(TokenTree::Ident(i)) if i == "derive")
+                    && group.stream().to_string().contains("serde ::");
+                let mut inner =
+                    Group::new(group.delimiter(), with_serde_path(group.stream(), config));
+                inner.set_span(group.span());
+                rewritten.push(TokenTree::Group(inner));
+                if is_serde_derive {
+                    rewritten.extend(quote!(#[serde(crate = #serde_path)]));
+                }
+            }
+            token => rewritten.push(token),
+        }
+    }
+    rewritten.into_iter().collect()
+}
+
+/// Removes the `serde` derives and `#[serde(...)]` attributes from generated code
+fn strip_serde(code: TokenStream) -> TokenStream {
+    let mut stripped = vec![];
+    let mut tokens = code.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                let attribute = match tokens.peek() {
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+                        group
+                    }
+                    _ => {
+                        stripped.push(TokenTree::Punct(punct));
+                        continue;
+                    }
+                };
+                let mut inner = attribute.stream().into_iter();
+                match (inner.next(), inner.next()) {
+                    (Some(TokenTree::Ident(name)), _) if name == "serde" => {
+                        tokens.next();
+                    }
+                    (Some(TokenTree::Ident(name)), Some(TokenTree::Group(derives)))
+                        if name == "derive" =>
+                    {
+                        tokens.next();
+                        let derives = derives
+                            .stream()
+                            .into_iter()
+                            .collect::<Vec<_>>()
+                            .split(|token| matches!(token, TokenTree::Punct(p) if p.as_char() == ','))
+                            .filter(|derive| {
+                                !matches!(derive.first(), Some(TokenTree::Ident(i)) if i == "serde")
+                            })
+                            .map(|derive| derive.iter().cloned().collect::<TokenStream>())
+                            .collect::<Vec<_>>();
+                        stripped.extend(quote!(#[derive(#(#derives),*)]));
+                    }
+                    _ => stripped.push(TokenTree::Punct(punct)),
+                }
+            }
+            TokenTree::Group(group) => {
+                let mut inner = Group::new(group.delimiter(), strip_serde(group.stream()));
+                inner.set_span(group.span());
+                stripped.push(TokenTree::Group(inner));
+            }
+            token => stripped.push(token),
+        }
+    }
+    stripped.into_iter().collect()
+}
+
+/// Whether `schemars::JsonSchema` is derived, which needs the `schemars` feature
+fn derives_json_schema(config: &GeneratorConfig) -> bool {
+    cfg!(feature = "schemars") && config.derive_json_schema
+}
+
+/// What the generation of an entity needs to know about the others
+#[derive(Default)]
+struct KnownEntities {
+    /// The entities that derive `sqlx::Type`, see `sqlx_types`
+    sqlx_types: HashSet<String>,
+    /// The entities that can't be `Eq`, see `partial_eq_only`
+    partial_eq_only: HashSet<String>,
+}
+
+/// Whether the type is a float itself, which `bitwise_float_eq` compares by its bits
+fn is_float(field_type: &FieldType) -> bool {
+    matches!(
+        field_type,
+        FieldType::Simple(Primitive::Float | Primitive::Double)
+    )
+}
+
+/// Whether a value of the type holds a float somewhere, directly or through one of the
+/// `partial_eq_only` entities
+fn holds_float(field_type: &FieldType, partial_eq_only: &HashSet<String>) -> bool {
+    match field_type {
+        FieldType::Simple(Primitive::Float | Primitive::Double) => true,
+        FieldType::Named(name) => partial_eq_only.contains(name),
+        FieldType::Array(Some(inner))
+        | FieldType::Object(Some(inner))
+        | FieldType::Boxed(inner) => holds_float(inner, partial_eq_only),
+        FieldType::Tuple(members) => members
+            .iter()
+            .any(|member| holds_float(member, partial_eq_only)),
+        _ => false,
+    }
+}
+
+/// The entities that only derive `PartialEq` as they hold a float, directly or through the
+/// entities they're made of, e.g. a `oneOf` with a variant that has a `double` field. With
+/// `bitwise_float_eq` structs compare their own float fields by their bits and implement `Eq`,
+/// unless a float is nested in another type (e.g. a `Vec<f64>`) which they'd compare with `==`.
+fn partial_eq_only(entities: &[Entity], config: &GeneratorConfig) -> HashSet<String> {
+    let mut partial_eq_only = HashSet::new();
+    loop {
+        let found = entities
+            .iter()
+            .filter(|entity| !partial_eq_only.contains(&entity.name))
+            .filter(|entity| {
+                let holds_float =
+                    |field_type: &FieldType| holds_float(field_type, &partial_eq_only);
+                match &entity.def {
+                    EntityDef::Struct(StructDef {
+                        properties,
+                        additional_properties,
+                        ..
+                    }) => {
+                        properties.values().any(|field| {
+                            holds_float(&field.field_type)
+                                && !(config.bitwise_float_eq && is_float(&field.field_type))
+                        }) || additional_properties.as_ref().is_some_and(holds_float)
+                    }
+                    EntityDef::OneOf { variants: all, .. } | EntityDef::AllOf(all) => {
+                        all.iter().any(|name| partial_eq_only.contains(name))
+                    }
+                    EntityDef::Tuple(members, tail) => members.iter().chain(tail).any(holds_float),
+                    EntityDef::MixedEnum(members) => members.iter().any(holds_float),
+                    EntityDef::Map(Some(value_type), _) => holds_float(value_type),
+                    EntityDef::Alias(target) => holds_float(target),
+                    EntityDef::Map(None, _) | EntityDef::Enum(_) => false,
+                }
+            })
+            .map(|entity| entity.name.clone())
+            .collect::<Vec<_>>();
+        if found.is_empty() {
+            return partial_eq_only;
+        }
+        partial_eq_only.extend(found);
+    }
+}
+
+/// Whether `const`s are plain values of their type instead of `MustBe!`, which needs serde and a
+/// format that asks for the type it reads
+fn plain_consts(config: &GeneratorConfig) -> bool {
+    config.no_serde || config.binary_compatible
+}
+
+/// Whether `sqlx::FromRow` and `sqlx::Type` are derived, which needs the `sqlx` feature
+fn derives_sqlx(config: &GeneratorConfig) -> bool {
+    cfg!(feature = "sqlx") && config.derive_sqlx
+}
+
+/// The entities that derive `sqlx::Type`: enums of plain values, an `Unknown(String)` variant
+/// can't be mapped to a database enum
+fn sqlx_types(entities: &[Entity], config: &GeneratorConfig) -> HashSet<String> {
+    if !derives_sqlx(config) {
+        return HashSet::new();
+    }
+    entities
+        .iter()
+        .filter(|entity| match &entity.def {
+            EntityDef::Enum(EnumDef { repr: Some(_), .. }) => true,
+            EntityDef::Enum(_) => !config.unknown_enum_variant,
+            _ => false,
+        })
+        .map(|entity| entity.name.clone())
+        .collect()
+}
+
+/// `#[sqlx(...)]` for the struct fields `FromRow` can't decode from a column of their own type:
+/// `MustBe!` consts are skipped, objects, tuples and entities that aren't in `sqlx_types` are
+/// read from a JSON column. Arrays of primitives map to database arrays.
+fn sqlx_attribute(
+    field_type: &FieldType,
+    optional: bool,
+    sqlx_types: &HashSet<String>,
+    config: &GeneratorConfig,
+) -> Option<TokenStream> {
+    if !derives_sqlx(config) {
+        return None;
+    }
+    let is_column = |field_type: &FieldType| match field_type {
+        FieldType::Simple(_) => true,
+        FieldType::Named(name) => sqlx_types.contains(name),
+        _ => false,
+    };
+    match field_type {
+        FieldType::Const(..) if plain_consts(config) => None,
+        FieldType::Const(..) => Some(quote!(#[sqlx(skip)])),
+        FieldType::Array(Some(inner)) if is_column(inner) => None,
+        field_type if is_column(field_type) => None,
+        _ if optional => Some(quote!(#[sqlx(json(nullable))])),
+        _ => Some(quote!(#[sqlx(json)])),
+    }
+}
+
+/// `#[schemars(...)]` for the fields whose type doesn't implement `JsonSchema` or whose schema
+/// would differ from the one they're generated from: `MustBe!` is described as its plain type
+/// with a `const` and base64 bytes as a string
+fn json_schema_attribute(
+    field_type: &FieldType,
+    optional: bool,
+    config: &GeneratorConfig,
+) -> Option<TokenStream> {
+    if !derives_json_schema(config) {
+        return None;
+    }
+    let with_optional = |plain_type: &str| {
+        if optional {
+            format!("Option<{}>", plain_type)
+        } else {
+            plain_type.to_string()
+        }
+    };
+    match field_type {
+        FieldType::Const(primitive, value) => {
+            let literal = match primitive {
+                Primitive::String | Primitive::Decimal => quote!(#value),
+                _ => value.parse().unwrap(),
+            };
+            if plain_consts(config) {
+                Some(quote!(#[schemars(extend("const" = #literal))]))
+            } else {
+                let with = with_optional(const_type(primitive, value));
+                Some(quote!(#[schemars(with = #with, extend("const" = #literal))]))
+            }
+        }
+        #[cfg(feature = "base64")]
+        FieldType::Simple(Primitive::Bytes) => {
+            let with = with_optional("String");
+            Some(quote!(#[schemars(with = #with)]))
+        }
+        _ => None,
+    }
+}
+
+/// Adds `schemars::JsonSchema` to the derives of every type that derives `serde::Serialize`
+fn with_json_schema(code: TokenStream) -> TokenStream {
+    let mut rewritten = vec![];
+    for token in code {
+        match token {
+            TokenTree::Group(group) => {
+                let mut stream = group.stream().into_iter();
+                let is_serialize_derive = group.delimiter() == Delimiter::Bracket
+                    && matches!(rewritten.last(), Some(TokenTree::Punct(p)) if p.as_char() == '#')
+                    && matches!(stream.next(), Some(TokenTree::Ident(i)) if i == "derive")
+                    && group.stream().to_string().contains("serde :: Serialize");
+                let inner = match stream.next() {
+                    Some(TokenTree::Group(derives)) if is_serialize_derive => {
+                        let derives = derives.stream();
+                        quote!(derive(#derives, schemars::JsonSchema))
+                    }
+                    _ => with_json_schema(group.stream()),
+                };
+                let mut inner = Group::new(group.delimiter(), inner);
+                inner.set_span(group.span());
+                rewritten.push(TokenTree::Group(inner));
+            }
+            token => rewritten.push(token),
+        }
+    }
+    rewritten.into_iter().collect()
+}
+
+/// Derives a variant identifier from the literal value of a `FieldType::Const`,
+/// e.g. `"in progress"` -> `InProgress`, `-1` -> `IntMinus1`, `true` -> `True`
+fn literal_variant_name(field_type: &FieldType, index: usize) -> String {
+    let name = match field_type {
+        FieldType::Const(Primitive::String, value) => value
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let (first, rest) = part.split_at(1);
+                first.to_uppercase() + rest
+            })
+            .collect::<String>(),
+        FieldType::Const(Primitive::Bool, value) => {
+            let (first, rest) = value.split_at(1);
+            first.to_uppercase() + rest
+        }
+        FieldType::Const(_, value) => format!("Int{}", value.replace('-', "Minus")),
+        FieldType::Simple(primitive) => format!("{:?}", primitive),
+        _ => String::new(),
+    };
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("Variant{}", index)
+    }
+}
+
+/// The variant identifiers of a classic enum, its `names` if it has them, otherwise the values.
+/// Those that aren't identifiers themselves are turned into one, e.g. `in-progress` ->
+/// `InProgress`. Names that collide get their index appended, as does `Unknown` when it's the
+/// catch-all variant of `unknown_enum_variant`.
+fn enum_variant_names(enum_def: &EnumDef, config: &GeneratorConfig) -> Vec<TokenStream> {
+    let literals = enum_def
+        .values
+        .iter()
+        .enumerate()
+        .map(
+            |(index, value)| match (enum_def.names.get(index), &enum_def.repr) {
+                (Some(name), _) => FieldType::Const(Primitive::String, name.clone()),
+                (None, Some(repr)) => FieldType::Const(repr.clone(), value.clone()),
+                (None, None) => FieldType::Const(Primitive::String, value.clone()),
+            },
+        )
+        .collect::<Vec<_>>();
+    // a variant with a field can't have a discriminant, a `repr` enum doesn't get the catch-all
+    let reserved = if config.unknown_enum_variant && enum_def.repr.is_none() {
+        &["Unknown"][..]
+    } else {
+        &[]
+    };
+    literal_variant_names(&literals, reserved)
+}
+
+/// The variant identifiers of the `values` of an enum, see `literal_variant_name`. Names that
+/// collide, e.g. those of `"True"` and `true` or `"a b"` and `"a-b"`, or are `reserved` get their
+/// index appended.
+fn literal_variant_names(values: &[FieldType], reserved: &[&str]) -> Vec<TokenStream> {
+    let mut names: Vec<String> = vec![];
+    for (index, value) in values.iter().enumerate() {
+        let name = literal_variant_name(value, index);
+        if names.contains(&name) || reserved.contains(&name.as_str()) {
+            names.push(format!("{}{}", name, index));
+        } else {
+            names.push(name);
+        }
+    }
+    names.iter().map(|name| name.parse().unwrap()).collect()
+}
+
+/// The doc comments of the variants `enum_variant_names` names, from `x-enumDescriptions`
+fn enum_variant_docs(enum_def: &EnumDef) -> Vec<Option<TokenStream>> {
+    (0..enum_def.values.len())
+        .map(|index| {
+            let doc = format!(" {}", enum_def.descriptions.get(index)?);
+            Some(quote!(#[doc = #doc]))
+        })
+        .collect()
+}
+
+/// Generates the statements checking `value` against the `constraints` of the property `name`,
+/// a violation returns early with an `Err(String)` describing it
+fn generate_constraint_checks(name: &str, constraints: &[Constraint]) -> TokenStream {
+    let checks = constraints.iter().map(|constraint| match constraint {
+        Constraint::MinItems(min) => {
+            let min = *min as usize;
+            let message = format!("`{}` must contain at least {} items", name, min);
+            quote! {
+                if value.len() < #min {
+                    return Err(#message.to_string());
+                }
+            }
+        }
+        Constraint::MaxItems(max) => {
+            let max = *max as usize;
+            let message = format!("`{}` must contain at most {} items", name, max);
+            quote! {
+                if value.len() > #max {
+                    return Err(#message.to_string());
+                }
+            }
+        }
+        Constraint::UniqueItems => {
+            let message = format!("`{}` must not contain duplicate items", name);
+            quote! {
+                if value.iter().enumerate().any(|(index, item)| value[..index].contains(item)) {
+                    return Err(#message.to_string());
+                }
+            }
+        }
+        Constraint::Not(values) => {
+            let values = values.iter().map(|value| {
+                let json = value.to_string();
+                quote!(serde_json::from_str::<serde_json::Value>(#json).unwrap())
+            });
+            quote! {
+                if let Ok(value) = serde_json::to_value(value) {
+                    if [#(#values),*].contains(&value) {
+                        return Err(format!("`{}` must not be `{}`", #name, value));
+                    }
+                }
+            }
+        }
+        Constraint::MinProperties(min) => {
+            let min = *min as usize;
+            let message = format!("`{}` must contain at least {} properties", name, min);
+            quote! {
+                if value.len() < #min {
+                    return Err(#message.to_string());
+                }
+            }
+        }
+        Constraint::MaxProperties(max) => {
+            let max = *max as usize;
+            let message = format!("`{}` must contain at most {} properties", name, max);
+            quote! {
+                if value.len() > #max {
+                    return Err(#message.to_string());
+                }
+            }
+        }
+        Constraint::PropertyNamePattern(pattern) => {
+            let message = format!("the keys of `{}` must match `{}`", name, pattern);
+            quote! {
+                {
+                    static PATTERN: std::sync::LazyLock<regex::Regex> =
+                        std::sync::LazyLock::new(|| regex::Regex::new(#pattern).unwrap());
+                    if let Some(key) = value.keys().find(|key| !PATTERN.is_match(key)) {
+                        return Err(format!("{}, got `{}`", #message, key));
+                    }
+                }
+            }
+        }
+    });
+    quote!(#(#checks)*)
+}
+
+/// Generates the statements checking the `dependentRequired` properties of a struct on `value`,
+/// returning the error `error` builds from the missing property and a message. Dependencies on
+/// required or undeclared properties always hold.
+fn generate_dependency_checks(
+    properties: &IndexMap<String, Field>,
+    dependent_required: &IndexMap<String, Vec<String>>,
+    error: impl Fn(&str, &str) -> TokenStream,
+) -> TokenStream {
+    let names = rust_field_names(properties);
+    let checks = dependent_required
+        .iter()
+        .filter_map(|(trigger, dependencies)| {
+            let trigger_field = properties.get(trigger)?;
+            let trigger_name: TokenStream = names[trigger].parse().unwrap();
+            let present = if trigger_field.optional {
+                quote!(value.#trigger_name.is_some())
+            } else {
+                quote!(true)
+            };
+            let missing = dependencies
+                .iter()
+                .filter(|dependency| properties.get(*dependency).is_some_and(|f| f.optional))
+                .map(|dependency| {
+                    let dependency_name: TokenStream = names[dependency].parse().unwrap();
+                    let message =
+                        format!("`{}` is required when `{}` is present", dependency, trigger);
+                    let error = error(dependency, &message);
+                    quote! {
+                        if value.#dependency_name.is_none() {
+                            return Err(#error);
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+            (!missing.is_empty()).then(|| {
+                quote! {
+                    if #present {
+                        #(#missing)*
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    quote!(#(#checks)*)
+}
+
+/// Generates the associated `check_<field>` and `deserialize_<field>` functions enforcing the
+/// constraints of a field and returns them together with the path for `deserialize_with`
+fn generate_field_validator(
+    entity_name: &str,
+    name: &str,
+    field_name: &str,
+    inner_type: &TokenStream,
+    optional: bool,
+    constraints: &[Constraint],
+) -> (TokenStream, String) {
+    let check_fn: TokenStream = format!("check_{}", field_name).parse().unwrap();
+    let deserialize_fn: TokenStream = format!("deserialize_{}", field_name).parse().unwrap();
+    let field_type = if optional {
+        quote!(Option<#inner_type>)
+    } else {
+        inner_type.clone()
+    };
+    let checks = generate_constraint_checks(name, constraints);
+    let check_value = if optional {
+        quote! {
+            if let Some(value) = &value {
+                Self::#check_fn(value).map_err(serde::de::Error::custom)?;
+            }
+        }
+    } else {
+        quote!(Self::#check_fn(&value).map_err(serde::de::Error::custom)?;)
+    };
+    let validator = quote! {
+        fn #check_fn(value: &#inner_type) -> Result<(), String> {
+            #checks
+            Ok(())
+        }
+
+        fn #deserialize_fn<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<#field_type, D::Error> {
+            let value = <#field_type as serde::Deserialize>::deserialize(deserializer)?;
+            #check_value
+            Ok(value)
+        }
+    };
+    (validator, format!("{}::{}", entity_name, deserialize_fn))
+}
+
+/// Generates the getter and, unless the field is a `MustBe!` that can't be changed anyway, the
+/// `_mut` getter of a private field
+fn generate_accessors(
+    field_name: &TokenStream,
+    field_type: &TokenStream,
+    is_const: bool,
+) -> TokenStream {
+    let getter_mut = (!is_const).then(|| {
+        let name_mut: TokenStream = format!("{}_mut", field_name).parse().unwrap();
+        quote! {
+            pub fn #name_mut(&mut self) -> &mut #field_type {
+                &mut self.#field_name
+            }
+        }
+    });
+    quote! {
+        pub fn #field_name(&self) -> &#field_type {
+            &self.#field_name
+        }
+
+        #getter_mut
+    }
+}
+
+fn generate_entity(entity: Entity, config: &GeneratorConfig, known: &KnownEntities) -> String {
     let identifier: TokenStream = entity.name.parse().unwrap();
+    let vis: TokenStream = config.visibility.parse().unwrap();
+    let eq = (!known.partial_eq_only.contains(&entity.name)).then(|| quote!(Eq,));
     let code = match entity.def {
         EntityDef::Struct(StructDef {
             properties,
             additional_properties,
+            dependent_required,
+            display,
+            ..
         }) => {
+            let validate = config.validate && !config.no_serde;
+            let dependency_checks = validate
+                .then(|| {
+                    generate_dependency_checks(
+                        &properties,
+                        &dependent_required,
+                        |_, message| quote!(#message.to_string()),
+                    )
+                })
+                .filter(|checks| !checks.is_empty());
+            let mut validations = vec![];
+            let dependency_validations = validate
+                .then(|| {
+                    generate_dependency_checks(
+                        &properties,
+                        &dependent_required,
+                        |field, message| {
+                            quote! {
+                                ValidationError {
+                                    field: #field.to_string(),
+                                    message: #message.to_string(),
+                                }
+                            }
+                        },
+                    )
+                })
+                .filter(|checks| !checks.is_empty())
+                .map(|checks| quote!(let value = self; #checks));
+            let mut field_names = vec![];
+            let mut validators = vec![];
+            let mut comparisons = vec![];
+            let mut has_float = false;
+            let mut debug_fields = vec![];
+            let mut has_write_only = false;
+            let mut new_params = vec![];
+            let mut new_fields = vec![];
+            let mut accessors = vec![];
+            let field_vis = if config.encapsulate {
+                quote!()
+            } else {
+                vis.clone()
+            };
+            let rust_names = rust_field_names(&properties);
+            let display_impl = display
+                .map(|template| generate_display(&identifier, &template, &properties, &rust_names));
             let mut fields = properties
                 .into_iter()
-                .map(|(name, field)| {
-                    let field_type: TokenStream =
-                        expand_field_type(field.field_type).parse().unwrap();
-                    let field_name: TokenStream = snake_case(&name).parse().unwrap();
-                    if field.optional {
-                        quote! {
-                            #[serde(rename = #name)]
-                            pub #field_name: Option<#field_type>
+                .map(|(name, mut field)| {
+                    // `x-rust-type` replaces whatever type the schema would generate to
+                    if let Some(rust_type) = field
+                        .extensions
+                        .get("x-rust-type")
+                        .and_then(serde_yaml::Value::as_str)
+                    {
+                        field.field_type = FieldType::Named(rust_type.to_string());
+                    }
+                    let is_float = is_float(&field.field_type);
+                    #[cfg(feature = "base64")]
+                    let is_bytes = matches!(field.field_type, FieldType::Simple(Primitive::Bytes));
+                    let is_const = matches!(field.field_type, FieldType::Const(..));
+                    let const_default = match &field.field_type {
+                        FieldType::Const(primitive, value) if plain_consts(config) => {
+                            const_value(primitive, value)
+                        }
+                        _ => quote!(Default::default()),
+                    };
+                    let invariant = match &field.field_type {
+                        FieldType::Const(_, value) if plain_consts(config) => {
+                            let doc = format!(" Always `{}`", value);
+                            quote!(#[doc = #doc])
+                        }
+                        _ => quote!(),
+                    };
+                    let borrow = config.string_type == StringType::Cow && field_borrows_str(&field);
+                    let json_schema =
+                        json_schema_attribute(&field.field_type, field.optional, config);
+                    let sqlx = sqlx_attribute(
+                        &field.field_type,
+                        field.optional,
+                        &known.sqlx_types,
+                        config,
+                    );
+                    let inner_type: TokenStream =
+                        expand_field_type(field.field_type, config).parse().unwrap();
+                    let field_type = if field.optional {
+                        quote!(Option<#inner_type>)
+                    } else {
+                        inner_type.clone()
+                    };
+                    let field_name: TokenStream = rust_names[&name].parse().unwrap();
+                    field_names.push(field_name.clone());
+                    has_float |= is_float;
+                    has_write_only |= field.write_only;
+                    new_fields.push(match (is_const, field.optional) {
+                        (_, true) => quote!(#field_name: None),
+                        (true, false) => quote!(#field_name: #const_default),
+                        (false, false) => {
+                            new_params.push(quote!(#field_name: #field_type));
+                            quote!(#field_name)
+                        }
+                    });
+                    accessors.push(generate_accessors(&field_name, &field_type, is_const));
+                    debug_fields.push(if field.write_only {
+                        quote!(.field(#name, &"[redacted]"))
+                    } else {
+                        quote!(.field(#name, &self.#field_name))
+                    });
+                    comparisons.push(match (is_float, field.optional) {
+                        (true, false) => {
+                            quote!(self.#field_name.to_bits() == other.#field_name.to_bits())
+                        }
+                        (true, true) => quote! {
+                            self.#field_name.map(|value| value.to_bits())
+                                == other.#field_name.map(|value| value.to_bits())
+                        },
+                        (false, _) => quote!(self.#field_name == other.#field_name),
+                    });
+                    let mut serde_args = vec![quote!(rename = #name)];
+                    if config.validate && !config.no_serde && !field.constraints.is_empty() {
+                        let (validator, deserialize_with) = generate_field_validator(
+                            &entity.name,
+                            &name,
+                            &field_name.to_string(),
+                            &inner_type,
+                            field.optional,
+                            &field.constraints,
+                        );
+                        validators.push(validator);
+                        let check_fn: TokenStream =
+                            format!("check_{}", field_name).parse().unwrap();
+                        let to_error = quote! {
+                            map_err(|message| ValidationError {
+                                field: #name.to_string(),
+                                message,
+                            })
+                        };
+                        validations.push(if field.optional {
+                            quote! {
+                                if let Some(value) = &self.#field_name {
+                                    Self::#check_fn(value).#to_error?;
+                                }
+                            }
+                        } else {
+                            quote!(Self::#check_fn(&self.#field_name).#to_error?;)
+                        });
+                        serde_args.push(quote!(deserialize_with = #deserialize_with));
+                        if field.optional {
+                            serde_args.push(quote!(default));
                         }
-                    } else {
-                        quote! {
-                            #[serde(rename = #name)]
-                            pub #field_name: #field_type
+                    }
+                    #[cfg(feature = "base64")]
+                    if is_bytes {
+                        let with = if field.optional {
+                            "base64_bytes::option"
+                        } else {
+                            "base64_bytes"
+                        };
+                        serde_args.push(quote!(with = #with));
+                        if field.optional {
+                            serde_args.push(quote!(default));
                         }
                     }
+                    if config.skip_serializing_none && field.optional {
+                        serde_args.push(quote!(skip_serializing_if = "Option::is_none"));
+                    }
+                    if borrow {
+                        serde_args.push(quote!(borrow));
+                    }
+                    quote! {
+                        #invariant
+                        #json_schema
+                        #sqlx
+                        #[serde(#(#serde_args),*)]
+                        #field_vis #field_name: #field_type
+                    }
                 })
                 .collect::<Vec<_>>();
+            // `deny_unknown_fields` is not supported in combination with `flatten`
+            let deny_unknown_fields =
+                if config.deny_unknown_fields && additional_properties.is_none() {
+                    quote!(#[serde(deny_unknown_fields)])
+                } else {
+                    quote!()
+                };
             if let Some(additional_properties) = additional_properties {
-                let field_type = expand_field_type(additional_properties)
+                let field_type = expand_field_type(additional_properties, config)
                     .parse::<TokenStream>()
                     .unwrap();
+                let map_path: TokenStream = config.map_type.path().parse().unwrap();
+                let map_type = quote!(#map_path<String, #field_type>);
+                accessors.push(generate_accessors(
+                    &quote!(additional_properties),
+                    &map_type,
+                    false,
+                ));
+                // a row has no columns beyond the declared ones
+                let sqlx = derives_sqlx(config).then(|| quote!(#[sqlx(skip)]));
+                let flatten = (!config.binary_compatible).then(|| quote!(flatten,));
                 fields.push(quote! {
-                    #[serde(flatten)]
-                    pub additional_properties: std::collections::HashMap<String, #field_type>
-                })
+                    #sqlx
+                    #[serde(#flatten default)]
+                    #field_vis additional_properties: #map_type
+                });
+                comparisons.push(quote!(
+                    self.additional_properties == other.additional_properties
+                ));
+                debug_fields.push(quote!(
+                    .field("additional_properties", &self.additional_properties)
+                ));
+                new_fields.push(quote!(additional_properties: Default::default()));
+                field_names.push(quote!(additional_properties));
             }
 
-            quote! {
-                #[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
-                pub struct #identifier {
-                    #(#fields),*
+            let from_row = derives_sqlx(config).then(|| quote!(sqlx::FromRow,));
+            // serde has no hook after a struct is deserialized, so the fields are read into an
+            // unchecked twin first that gets converted with `try_from`
+            let (try_from, dependency_check) = match dependency_checks {
+                Some(checks) => {
+                    let unchecked_name = format!("{}Unchecked", entity.name);
+                    let unchecked: TokenStream = unchecked_name.parse().unwrap();
+                    // the twin shares the `#[sqlx(...)]` and `#[schemars(...)]` field attributes,
+                    // which need the derives. schemars describes what's deserialized through
+                    // `try_from` with the twin, under the name of the struct.
+                    let struct_name = &entity.name;
+                    let json_schema = derives_json_schema(config).then(|| {
+                        quote! {
+                            #[derive(schemars::JsonSchema)]
+                            #[schemars(rename = #struct_name)]
+                        }
+                    });
+                    let check = quote! {
+                        #[derive(#from_row serde::Deserialize)]
+                        #json_schema
+                        #deny_unknown_fields
+                        struct #unchecked {
+                            #(#fields),*
+                        }
+
+                        impl TryFrom<#unchecked> for #identifier {
+                            type Error = String;
+
+                            fn try_from(value: #unchecked) -> Result<Self, Self::Error> {
+                                #checks
+                                Ok(Self {
+                                    #(#field_names: value.#field_names),*
+                                })
+                            }
+                        }
+                    };
+                    (quote!(#[serde(try_from = #unchecked_name)]), Some(check))
+                }
+                None => (deny_unknown_fields, None),
+            };
+
+            let accessors = config.encapsulate.then(|| {
+                quote! {
+                    impl #identifier {
+                        #(#accessors)*
+                    }
+                }
+            });
+
+            // consts only have a single value so they aren't parameters
+            let constructor = (!new_params.is_empty()).then(|| {
+                quote! {
+                    impl #identifier {
+                        /// Creates a value from the required fields, the optional ones are `None`
+                        pub fn new(#(#new_params),*) -> Self {
+                            Self {
+                                #(#new_fields),*
+                            }
+                        }
+                    }
+                }
+            });
+
+            let validate_fn =
+                (!validations.is_empty() || dependency_validations.is_some()).then(|| {
+                    quote! {
+                        /// Checks the constraints of the schema on the fields of this value, e.g.
+                        /// after it was built or changed in code rather than deserialized
+                        pub fn validate(&self) -> Result<(), ValidationError> {
+                            #(#validations)*
+                            #dependency_validations
+                            Ok(())
+                        }
+                    }
+                });
+            let validators = (!validators.is_empty() || validate_fn.is_some()).then(|| {
+                quote! {
+                    impl #identifier {
+                        #validate_fn
+                        #(#validators)*
+                    }
+                }
+            });
+
+            // `writeOnly` values (e.g. passwords) must not end up in logs
+            let debug_impl = (config.redact_write_only && has_write_only).then(|| {
+                let name = entity.name.as_str();
+                quote! {
+                    impl std::fmt::Debug for #identifier {
+                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                            f.debug_struct(#name)
+                                #(#debug_fields)*
+                                .finish()
+                        }
+                    }
+                }
+            });
+            let debug_derive = debug_impl.is_none().then(|| quote!(Debug,));
+
+            // floats are compared by their bits, which makes `NaN == NaN` and allows `Eq`. A float
+            // nested in another type would still be compared with `==`, those structs aren't `Eq`
+            if config.bitwise_float_eq && has_float && eq.is_some() {
+                quote! {
+                    #[derive(#debug_derive Clone, #from_row serde::Deserialize, serde::Serialize)]
+                    #try_from
+                    #vis struct #identifier {
+                        #(#fields),*
+                    }
+
+                    impl PartialEq for #identifier {
+                        fn eq(&self, other: &Self) -> bool {
+                            #(#comparisons)&&*
+                        }
+                    }
+
+                    impl Eq for #identifier {}
+
+                    #constructor
+                    #accessors
+                    #debug_impl
+                    #display_impl
+                    #validators
+                    #dependency_check
+                }
+            } else {
+                quote! {
+                    #[derive(#debug_derive Clone, #eq PartialEq, #from_row serde::Deserialize, serde::Serialize)]
+                    #try_from
+                    #vis struct #identifier {
+                        #(#fields),*
+                    }
+
+                    #constructor
+                    #accessors
+                    #debug_impl
+                    #display_impl
+                    #validators
+                    #dependency_check
                 }
             }
         }
@@ -106,70 +1944,380 @@ fn generate_entity(entity: Entity) -> String {
         EntityDef::OneOf {
             discriminant,
             variants,
+            tags,
         } => {
-            let variants = variants.into_iter().map(|variant| {
-                let variant_name: TokenStream = variant.parse().unwrap();
+            let variant_names = variants
+                .iter()
+                .map(|variant| variant.parse::<TokenStream>().unwrap())
+                .collect::<Vec<_>>();
+            let variants = variant_names.iter().map(|variant_name| {
+                let rename = tags.get(&variant_name.to_string()).map(|tag| {
+                    quote! {
+                        #[serde(rename = #tag)]
+                    }
+                });
                 quote! {
+                    #rename
                     #variant_name(#variant_name)
                 }
             });
+            // gets the value of a variant back out, a union holding another variant is the error
+            let try_from_variants = |other_variant: bool| {
+                let fallback =
+                    (variant_names.len() > 1 || other_variant).then(|| quote!(other => Err(other)));
+                variant_names
+                    .iter()
+                    .map(|variant_name| {
+                        quote! {
+                            impl TryFrom<#identifier> for #variant_name {
+                                type Error = #identifier;
+
+                                fn try_from(value: #identifier) -> Result<Self, Self::Error> {
+                                    match value {
+                                        #identifier::#variant_name(value) => Ok(value),
+                                        #fallback
+                                    }
+                                }
+                            }
+                        }
+                    })
+                    .collect::<TokenStream>()
+            };
             if let Some(discriminant) = discriminant {
+                let try_from_variants = try_from_variants(false);
+                let tags = variant_names.iter().map(|variant_name| {
+                    let variant = variant_name.to_string();
+                    let tag = tags.get(&variant).unwrap_or(&variant);
+                    quote! {
+                        Self::#variant_name(_) => #tag
+                    }
+                });
                 quote! {
-                    #[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+                    #[derive(Debug, Clone, #eq PartialEq, serde::Deserialize, serde::Serialize)]
                     #[serde(tag = #discriminant)]
-                    pub enum #identifier {
+                    #vis enum #identifier {
                         #(#variants),*
                     }
 
+                    impl #identifier {
+                        /// The value of the discriminator field that selects this variant
+                        pub fn discriminator(&self) -> &'static str {
+                            match self {
+                                #(#tags),*
+                            }
+                        }
+                    }
+
+                    #try_from_variants
                 }
             } else {
-                quote! {
-                    #[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
-                    #[serde(untagged)]
-                    pub enum #identifier {
-                        #(#variants),*
+                let from_json = (!config.no_serde
+                    && config.message_enum.as_ref() == Some(&entity.name))
+                .then(|| {
+                    quote! {
+                        impl #identifier {
+                            /// Deserializes a message payload into the first variant it matches
+                            pub fn from_json(json: &str) -> serde_json::Result<Self> {
+                                serde_json::from_str(json)
+                            }
+                        }
+                    }
+                });
+                match variant_names.as_slice() {
+                    // a single variant needs no enum, the newtype (de)serializes as the variant
+                    [variant_name] if !config.untagged_other_variant => quote! {
+                        #[derive(Debug, Clone, #eq PartialEq, serde::Deserialize, serde::Serialize)]
+                        #[serde(transparent)]
+                        #vis struct #identifier(#vis #variant_name);
+
+                        #from_json
+                    },
+                    _ => {
+                        let other = config
+                            .untagged_other_variant
+                            .then(|| quote!(Other(serde_json::Value)));
+                        let variants = variants.chain(other);
+                        let try_from_variants = try_from_variants(config.untagged_other_variant);
+                        quote! {
+                            #[derive(Debug, Clone, #eq PartialEq, serde::Deserialize, serde::Serialize)]
+                            #[serde(untagged)]
+                            #vis enum #identifier {
+                                #(#variants),*
+                            }
+
+                            #try_from_variants
+                            #from_json
+                        }
                     }
                 }
             }
         }
         EntityDef::AllOf(all_of) => {
-            let flattened_structs = all_of.into_iter().map(|entity| {
-                let field_name = snake_case(&entity).parse::<TokenStream>().unwrap();
-                let field_type = entity.parse::<TokenStream>().unwrap();
-                quote! {
-                    #[serde(flatten)]
-                    pub #field_name: #field_type
-                }
-            });
+            let mut conversions = vec![];
+            let flattened_structs = all_of
+                .into_iter()
+                .map(|entity| {
+                    let field_name = snake_case(&entity).parse::<TokenStream>().unwrap();
+                    let field_type = entity.parse::<TokenStream>().unwrap();
+                    conversions.push(quote! {
+                        impl From<&#identifier> for #field_type {
+                            fn from(value: &#identifier) -> Self {
+                                value.#field_name.clone()
+                            }
+                        }
+                    });
+                    quote! {
+                        #[serde(flatten)]
+                        #vis #field_name: #field_type
+                    }
+                })
+                .collect::<Vec<_>>();
             quote! {
-                #[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
-                pub struct #identifier {
+                #[derive(Debug, Clone, #eq PartialEq, serde::Deserialize, serde::Serialize)]
+                #vis struct #identifier {
                     #(#flattened_structs),*
                 }
+
+                #(#conversions)*
+            }
+        }
+        EntityDef::Enum(EnumDef {
+            values,
+            names,
+            descriptions,
+            repr: Some(repr),
+        }) => {
+            let enum_def = EnumDef {
+                values,
+                names,
+                descriptions,
+                repr: Some(repr.clone()),
+            };
+            // `unknown_enum_variant` doesn't apply, a variant with a field can't have a discriminant
+            let variants = enum_variant_names(&enum_def, config);
+            let docs = enum_variant_docs(&enum_def);
+            let values = enum_def.values;
+            let repr_type = expand_field_type(FieldType::Simple(repr), config);
+            let repr: TokenStream = repr_type.parse().unwrap();
+            let discriminants = values
+                .iter()
+                .map(|value| value.parse::<TokenStream>().unwrap())
+                .collect::<Vec<_>>();
+            let error = format!("unknown {} value: {{}}", entity.name);
+            let sqlx_type = derives_sqlx(config).then(|| quote!(sqlx::Type,));
+            quote! {
+                #[derive(Debug, Clone, Copy, Eq, PartialEq, #sqlx_type serde::Deserialize, serde::Serialize)]
+                #[serde(try_from = #repr_type, into = #repr_type)]
+                #[repr(#repr)]
+                #vis enum #identifier {
+                    #(#docs #variants = #discriminants),*
+                }
+
+                impl From<#identifier> for #repr {
+                    fn from(value: #identifier) -> Self {
+                        value as #repr
+                    }
+                }
+
+                impl TryFrom<#repr> for #identifier {
+                    type Error = String;
+
+                    fn try_from(value: #repr) -> Result<Self, Self::Error> {
+                        match value {
+                            #(#discriminants => Ok(Self::#variants),)*
+                            _ => Err(format!(#error, value)),
+                        }
+                    }
+                }
+            }
+        }
+        EntityDef::Enum(enum_def) => {
+            let variants = enum_variant_names(&enum_def, config);
+            let docs = enum_variant_docs(&enum_def);
+            let values = enum_def.values;
+            if config.unknown_enum_variant {
+                quote! {
+                    #[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+                    #[serde(from = "String", into = "String")]
+                    #vis enum #identifier {
+                        #(#docs #variants,)*
+                        Unknown(String)
+                    }
+
+                    impl From<&str> for #identifier {
+                        fn from(value: &str) -> Self {
+                            match value {
+                                #(#values => Self::#variants,)*
+                                _ => Self::Unknown(value.to_string()),
+                            }
+                        }
+                    }
+
+                    impl From<String> for #identifier {
+                        fn from(value: String) -> Self {
+                            Self::from(value.as_str())
+                        }
+                    }
+
+                    impl From<#identifier> for String {
+                        fn from(value: #identifier) -> Self {
+                            match value {
+                                #(#identifier::#variants => #values.to_string(),)*
+                                #identifier::Unknown(value) => value,
+                            }
+                        }
+                    }
+                }
+            } else {
+                let error = format!("unknown {} value: {{}}", entity.name);
+                let sqlx_type = derives_sqlx(config).then(|| quote!(sqlx::Type,));
+                let sqlx_renames = values
+                    .iter()
+                    .map(|value| derives_sqlx(config).then(|| quote!(#[sqlx(rename = #value)])));
+                quote! {
+                    #[derive(Debug, Clone, Eq, PartialEq, #sqlx_type serde::Deserialize, serde::Serialize)]
+                    #vis enum #identifier {
+                        #(#docs #[serde(rename = #values)] #sqlx_renames #variants),*
+                    }
+
+                    impl TryFrom<&str> for #identifier {
+                        type Error = String;
+
+                        fn try_from(value: &str) -> Result<Self, Self::Error> {
+                            match value {
+                                #(#values => Ok(Self::#variants),)*
+                                _ => Err(format!(#error, value)),
+                            }
+                        }
+                    }
+                }
             }
         }
-        EntityDef::Enum(EnumDef { values }) => {
-            let variants = values.into_iter().map(|value| {
-                let value: TokenStream = value.parse().unwrap();
+        EntityDef::Tuple(members, Some(tail)) => {
+            generate_variadic_tuple(&entity.name, members, tail, eq, config)
+        }
+        EntityDef::Tuple(members, None) => {
+            let members = members.into_iter().map(|member| {
+                let member: TokenStream = expand_field_type(member, config).parse().unwrap();
                 quote! {
-                    #value
+                    #vis #member
                 }
             });
+            quote! {
+                #[derive(Debug, Clone, #eq PartialEq, serde::Deserialize, serde::Serialize)]
+                #vis struct #identifier(#(#members),*);
+            }
+        }
+        // a single `const` (or a one value `enum`) needs no enum, the newtype only holds the value
+        EntityDef::MixedEnum(values) if matches!(values.as_slice(), [FieldType::Const(..)]) => {
+            let value = values.into_iter().next().unwrap();
+            let json_schema = json_schema_attribute(&value, false, config);
+            let default = match &value {
+                FieldType::Const(primitive, value) if plain_consts(config) => {
+                    let value = const_value(primitive, value);
+                    quote!(Self(#value))
+                }
+                _ => quote!(Self(Default::default())),
+            };
+            let value_type: TokenStream = expand_field_type(value, config).parse().unwrap();
             quote! {
                 #[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
-                pub enum #identifier {
+                #[serde(transparent)]
+                #vis struct #identifier(#json_schema #vis #value_type);
+
+                impl Default for #identifier {
+                    fn default() -> Self {
+                        #default
+                    }
+                }
+            }
+        }
+        EntityDef::MixedEnum(values) => {
+            // the untagged variants are told apart by their `MustBe!`s
+            let config = &GeneratorConfig {
+                binary_compatible: false,
+                ..config.clone()
+            };
+            let conversions = integer_enum_conversions(&entity.name, &values, config);
+            let variant_names = literal_variant_names(&values, &[]);
+            let variants = values
+                .into_iter()
+                .zip(variant_names)
+                .map(|(value, variant_name)| {
+                    let json_schema = json_schema_attribute(&value, false, config);
+                    let value_type: TokenStream = expand_field_type(value, config).parse().unwrap();
+                    quote! {
+                        #variant_name(#json_schema #value_type)
+                    }
+                });
+            quote! {
+                #[derive(Debug, Clone, #eq PartialEq, serde::Deserialize, serde::Serialize)]
+                #[serde(untagged)]
+                #vis enum #identifier {
                     #(#variants),*
                 }
+
+                #conversions
+            }
+        }
+        EntityDef::Map(value_type, constraints) => {
+            let value_type: TokenStream = value_type
+                .map(|value_type| expand_field_type(value_type, config))
+                .unwrap_or_else(|| "serde_json::Value".into())
+                .parse()
+                .unwrap();
+            let map_path: TokenStream = config.map_type.path().parse().unwrap();
+            let map_type = quote!(#map_path<String, #value_type>);
+            let (deserialize_with, validator) =
+                if config.validate && !config.no_serde && !constraints.is_empty() {
+                    let (validator, deserialize_with) = generate_field_validator(
+                        &entity.name,
+                        &entity.name,
+                        &snake_case(&entity.name),
+                        &map_type,
+                        false,
+                        &constraints,
+                    );
+                    (
+                        Some(quote!(#[serde(deserialize_with = #deserialize_with)])),
+                        Some(quote! {
+                            impl #identifier {
+                                #validator
+                            }
+                        }),
+                    )
+                } else {
+                    (None, None)
+                };
+            quote! {
+                #[derive(Debug, Clone, Default, #eq PartialEq, serde::Deserialize, serde::Serialize)]
+                #[serde(transparent)]
+                #vis struct #identifier(#deserialize_with #vis #map_type);
+
+                #validator
+            }
+        }
+        EntityDef::Alias(target) => {
+            let target: TokenStream = expand_field_type(target, config).parse().unwrap();
+            quote! {
+                #vis type #identifier = #target;
             }
         }
     };
-    code.to_string()
+    let code = if derives_json_schema(config) {
+        with_json_schema(code)
+    } else {
+        code
+    };
+    if config.no_serde {
+        strip_serde(code).to_string()
+    } else {
+        with_serde_path(code, config).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::parser::{Field, StructDef};
-
     use super::*;
 
     #[test]
@@ -181,6 +2329,9 @@ mod test {
                     Field {
                         field_type: FieldType::Named("FieldEntityName".to_string()),
                         optional: true,
+                        constraints: vec![],
+                        write_only: false,
+                        extensions: Default::default(),
                     },
                 ),
                 (
@@ -188,18 +2339,24 @@ mod test {
                     Field {
                         field_type: FieldType::Const(Primitive::String, "constValue".to_string()),
                         optional: false,
+                        constraints: vec![],
+                        write_only: false,
+                        extensions: Default::default(),
                     },
                 ),
             ]
             .into_iter()
             .collect(),
             additional_properties: Some(FieldType::Array(None)),
+            example: None,
+            dependent_required: IndexMap::new(),
+            display: None,
         });
         let entity = Entity {
             name: "StructEntity".to_string(),
             def: struct_def,
         };
-        let code = generate_entity(entity);
+        let code = generate_entity(entity, &GeneratorConfig::default(), &Default::default());
         println!("{}", code);
         assert!(code.contains("pub struct StructEntity"));
         assert!(code
@@ -208,6 +2365,9 @@ mod test {
         assert!(code
             .replace(" ", "")
             .contains("const_field:monostate::MustBe!(\"constValue\")"));
+        assert!(code.replace(" ", "").contains(
+            "#[serde(flatten,default)]pubadditional_properties:std::collections::HashMap"
+        ));
     }
 
     #[test]
@@ -215,14 +2375,591 @@ mod test {
         let enum_def = EntityDef::OneOf {
             discriminant: Some("type".to_string()),
             variants: vec!["Variant1".to_string(), "Variant2".to_string()],
+            tags: [("Variant2".to_string(), "variant2".to_string())].into(),
         };
         let entity = Entity {
             name: "EnumEntity".to_string(),
             def: enum_def,
         };
-        let code = generate_entity(entity);
+        let code = generate_entity(entity, &GeneratorConfig::default(), &Default::default());
         println!("{}", code);
         assert!(code.contains("pub enum EnumEntity"));
         assert!(code.replace(" ", "").contains("#[serde(tag=\"type\")]"));
+        assert!(code
+            .replace(" ", "")
+            .contains("Self::Variant1(_)=>\"Variant1\",Self::Variant2(_)=>\"variant2\""));
+        assert!(code
+            .replace(" ", "")
+            .contains("#[serde(rename=\"variant2\")]Variant2(Variant2)"));
+        // the variant structs rename their own fields, a container `rename_all` would rename the
+        // variants and the fields of struct variants a second time
+        assert!(!code.contains("rename_all"));
+    }
+
+    #[test]
+    fn test_generate_untagged_other_variant() {
+        let entity = Entity {
+            name: "Either".to_string(),
+            def: EntityDef::OneOf {
+                discriminant: None,
+                variants: vec!["Left".to_string(), "Right".to_string()],
+                tags: Default::default(),
+            },
+        };
+        let config = GeneratorConfig {
+            untagged_other_variant: true,
+            ..Default::default()
+        };
+        let code = generate_entity(entity, &config, &Default::default()).replace(' ', "");
+        assert!(code.contains("Left(Left),Right(Right),Other(serde_json::Value)}"));
+    }
+
+    #[test]
+    fn test_generate_tuple_struct() {
+        let entity = Entity {
+            name: "TupleEntity".to_string(),
+            def: EntityDef::Tuple(
+                vec![
+                    FieldType::Simple(Primitive::String),
+                    FieldType::Boxed(Box::new(FieldType::Named("Node".to_string()))),
+                ],
+                None,
+            ),
+        };
+        let code = generate_entity(entity, &GeneratorConfig::default(), &Default::default());
+        assert!(code
+            .replace(" ", "")
+            .contains("pubstructTupleEntity(pubString,pubBox<Node>);"));
+    }
+
+    #[test]
+    fn test_generate_mixed_enum() {
+        let entity = Entity {
+            name: "Mixed".to_string(),
+            def: EntityDef::MixedEnum(vec![
+                FieldType::Const(Primitive::String, "in progress".to_string()),
+                FieldType::Const(Primitive::Long, "-1".to_string()),
+                FieldType::Const(Primitive::Bool, "true".to_string()),
+            ]),
+        };
+        let code = generate_entity(entity, &GeneratorConfig::default(), &Default::default())
+            .replace(' ', "");
+        assert!(code.contains("#[serde(untagged)]"));
+        assert!(code.contains(
+            "InProgress(monostate::MustBe!(\"in progress\"))"
+                .replace(' ', "")
+                .as_str()
+        ));
+        assert!(code.contains("IntMinus1(monostate::MustBe!(-1i64))"));
+        assert!(code.contains("True(monostate::MustBe!(true))"));
+    }
+
+    #[test]
+    fn test_generate_enum_unknown_variant() {
+        let entity = Entity {
+            name: "Status".to_string(),
+            def: EntityDef::Enum(EnumDef {
+                values: vec!["Active".to_string(), "Inactive".to_string()],
+                names: vec![],
+                descriptions: vec![],
+                repr: None,
+            }),
+        };
+        let config = GeneratorConfig {
+            unknown_enum_variant: true,
+            ..Default::default()
+        };
+        let code = generate_entity(entity.clone(), &config, &Default::default()).replace(' ', "");
+        assert!(code.contains("Unknown(String)"));
+        assert!(code.contains("#[serde(from=\"String\",into=\"String\")]"));
+
+        let code = generate_entity(entity, &GeneratorConfig::default(), &Default::default())
+            .replace(' ', "");
+        assert!(!code.contains("Unknown(String)"));
+        assert!(code.contains("implTryFrom<&str>forStatus"));
+    }
+
+    #[test]
+    fn test_generate_deny_unknown_fields() {
+        let config = GeneratorConfig {
+            deny_unknown_fields: true,
+            ..Default::default()
+        };
+        let plain_struct = Entity {
+            name: "Plain".to_string(),
+            def: EntityDef::Struct(StructDef {
+                properties: IndexMap::new(),
+                additional_properties: None,
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+        };
+        let code = generate_entity(plain_struct, &config, &Default::default()).replace(' ', "");
+        assert!(code.contains("#[serde(deny_unknown_fields)]"));
+
+        let flattened_struct = Entity {
+            name: "Flattened".to_string(),
+            def: EntityDef::Struct(StructDef {
+                properties: IndexMap::new(),
+                additional_properties: Some(FieldType::Object(None)),
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+        };
+        let code = generate_entity(flattened_struct, &config, &Default::default()).replace(' ', "");
+        assert!(!code.contains("deny_unknown_fields"));
+
+        let all_of = Entity {
+            name: "Composite".to_string(),
+            def: EntityDef::AllOf(vec!["Base".to_string()]),
+        };
+        let code = generate_entity(all_of, &config, &Default::default()).replace(' ', "");
+        assert!(!code.contains("deny_unknown_fields"));
+    }
+
+    #[test]
+    fn test_generate_all_of_conversions() {
+        let entity = Entity {
+            name: "Composite".to_string(),
+            def: EntityDef::AllOf(vec!["Base".to_string(), "Extra".to_string()]),
+        };
+        let code = generate_entity(entity, &GeneratorConfig::default(), &Default::default())
+            .replace(' ', "");
+        assert!(code.contains(
+            "implFrom<&Composite>forBase{fnfrom(value:&Composite)->Self{value.base.clone()}}"
+        ));
+        assert!(code.contains("implFrom<&Composite>forExtra"));
+    }
+
+    #[test]
+    fn test_generate_skip_serializing_none() {
+        let entity = Entity {
+            name: "Optionals".to_string(),
+            def: EntityDef::Struct(StructDef {
+                properties: vec![(
+                    "maybe".to_string(),
+                    Field {
+                        field_type: FieldType::Simple(Primitive::String),
+                        optional: true,
+                        constraints: vec![],
+                        write_only: false,
+                        extensions: Default::default(),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+                additional_properties: None,
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+        };
+        let code = generate_entity(
+            entity.clone(),
+            &GeneratorConfig::default(),
+            &Default::default(),
+        )
+        .replace(' ', "");
+        assert!(code.contains("skip_serializing_if=\"Option::is_none\""));
+
+        let config = GeneratorConfig {
+            skip_serializing_none: false,
+            ..Default::default()
+        };
+        let code = generate_entity(entity, &config, &Default::default()).replace(' ', "");
+        assert!(!code.contains("skip_serializing_if"));
+    }
+
+    #[test]
+    fn test_expand_decimal() {
+        assert_eq!(
+            expand_field_type(
+                FieldType::Simple(Primitive::Decimal),
+                &GeneratorConfig::default()
+            ),
+            "rust_decimal::Decimal"
+        );
+    }
+
+    #[test]
+    fn test_expand_decimal_const() {
+        let field_type = FieldType::Const(Primitive::Decimal, "0.1".into());
+        assert_eq!(
+            expand_field_type(field_type.clone(), &GeneratorConfig::default()),
+            "monostate::MustBe!(\"0.1\")"
+        );
+        let config = GeneratorConfig {
+            no_serde: true,
+            ..Default::default()
+        };
+        assert_eq!(expand_field_type(field_type, &config), "String");
+    }
+
+    #[test]
+    fn test_generate_map_newtype() {
+        let entity = Entity {
+            name: "Scores".to_string(),
+            def: EntityDef::Map(Some(FieldType::Simple(Primitive::Int)), vec![]),
+        };
+        let code = generate_entity(entity, &GeneratorConfig::default(), &Default::default())
+            .replace(' ', "");
+        assert!(code.contains("#[serde(transparent)]"));
+        assert!(code.contains("pubstructScores(pubstd::collections::HashMap<String,i32>);"));
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_generate_base64_bytes() {
+        let mut properties = IndexMap::new();
+        for (name, optional) in [("payload", false), ("signature", true)] {
+            properties.insert(
+                name.to_string(),
+                Field {
+                    optional,
+                    field_type: FieldType::Simple(Primitive::Bytes),
+                    constraints: vec![],
+                    write_only: false,
+                    extensions: Default::default(),
+                },
+            );
+        }
+        let entity = Entity {
+            name: "Blob".to_string(),
+            def: EntityDef::Struct(StructDef {
+                properties,
+                additional_properties: None,
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+        };
+        let code = generate_code(vec![entity], &GeneratorConfig::default()).replace(' ', "");
+        assert!(code.contains("#[serde(rename=\"payload\",with=\"base64_bytes\")]"));
+        assert!(code.contains("with=\"base64_bytes::option\",default"));
+        assert!(code.contains("modbase64_bytes{"));
+    }
+
+    #[test]
+    fn test_generate_visibility() {
+        let config = GeneratorConfig {
+            visibility: "pub(crate)".to_string(),
+            ..Default::default()
+        };
+        let mut properties = IndexMap::new();
+        properties.insert(
+            "name".to_string(),
+            Field {
+                optional: false,
+                field_type: FieldType::Simple(Primitive::String),
+                constraints: vec![],
+                write_only: false,
+                extensions: Default::default(),
+            },
+        );
+        let entities = [
+            EntityDef::Struct(StructDef {
+                properties,
+                additional_properties: Some(FieldType::Object(None)),
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+            EntityDef::Enum(EnumDef {
+                values: vec!["A".to_string()],
+                names: vec![],
+                descriptions: vec![],
+                repr: None,
+            }),
+            EntityDef::Tuple(vec![FieldType::Simple(Primitive::Bool)], None),
+            EntityDef::Alias(FieldType::Named("Other".to_string())),
+        ];
+        let code = entities
+            .into_iter()
+            .map(|def| {
+                let entity = Entity {
+                    name: "Entity".to_string(),
+                    def,
+                };
+                generate_entity(entity, &config, &Default::default()).replace(' ', "")
+            })
+            .collect::<Vec<_>>();
+        assert!(code[0].contains("pub(crate)structEntity{"));
+        assert!(code[0].contains("pub(crate)name:String"));
+        assert!(code[0].contains("pub(crate)additional_properties:"));
+        assert!(code[1].contains("pub(crate)enumEntity{"));
+        assert!(code[2].contains("pub(crate)structEntity(pub(crate)bool);"));
+        assert!(code[3].contains("pub(crate)typeEntity=Other;"));
+        assert!(!code.concat().contains("pubstruct"));
+    }
+
+    #[test]
+    fn test_generate_bitwise_float_eq() {
+        let config = GeneratorConfig {
+            bitwise_float_eq: true,
+            ..Default::default()
+        };
+        let mut properties = IndexMap::new();
+        for (name, field_type, optional) in [
+            ("x", FieldType::Simple(Primitive::Double), false),
+            ("y", FieldType::Simple(Primitive::Float), true),
+            ("label", FieldType::Simple(Primitive::String), false),
+        ] {
+            properties.insert(
+                name.to_string(),
+                Field {
+                    optional,
+                    field_type,
+                    constraints: vec![],
+                    write_only: false,
+                    extensions: Default::default(),
+                },
+            );
+        }
+        let entity = Entity {
+            name: "Point".to_string(),
+            def: EntityDef::Struct(StructDef {
+                properties,
+                additional_properties: None,
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+        };
+        let code = generate_entity(entity, &config, &Default::default()).replace(' ', "");
+        assert!(!code.contains("PartialEq,"));
+        assert!(code.contains("implPartialEqforPoint"));
+        assert!(code.contains("self.x.to_bits()==other.x.to_bits()"));
+        assert!(code.contains("self.y.map(|value|value.to_bits())"));
+        assert!(code.contains("self.label==other.label"));
+        assert!(code.contains("implEqforPoint{}"));
+    }
+
+    #[test]
+    fn test_bitwise_float_eq_nested_float() {
+        let config = GeneratorConfig {
+            bitwise_float_eq: true,
+            ..Default::default()
+        };
+        let mut properties = IndexMap::new();
+        properties.insert(
+            "samples".to_string(),
+            Field {
+                optional: false,
+                field_type: FieldType::Array(Some(Box::new(FieldType::Simple(Primitive::Double)))),
+                constraints: vec![],
+                write_only: false,
+                extensions: Default::default(),
+            },
+        );
+        let entities = vec![Entity {
+            name: "Series".to_string(),
+            def: EntityDef::Struct(StructDef {
+                properties,
+                additional_properties: None,
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+        }];
+        let code = generate_code(entities, &config).replace(' ', "");
+        assert!(code.contains("#[derive(Debug,Clone,PartialEq,"));
+        assert!(!code.contains("Eq,PartialEq"));
+        assert!(!code.contains("implEqforSeries"));
+    }
+
+    #[test]
+    fn test_generate_constructor() {
+        let mut properties = IndexMap::new();
+        for (name, field_type, optional) in [
+            ("name", FieldType::Simple(Primitive::String), false),
+            ("age", FieldType::Simple(Primitive::Int), true),
+            (
+                "kind",
+                FieldType::Const(Primitive::String, "user".to_string()),
+                false,
+            ),
+        ] {
+            properties.insert(
+                name.to_string(),
+                Field {
+                    optional,
+                    field_type,
+                    constraints: vec![],
+                    write_only: false,
+                    extensions: Default::default(),
+                },
+            );
+        }
+        let entity = Entity {
+            name: "User".to_string(),
+            def: EntityDef::Struct(StructDef {
+                properties,
+                additional_properties: Some(FieldType::Simple(Primitive::String)),
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+        };
+        let code = generate_entity(entity, &GeneratorConfig::default(), &Default::default())
+            .replace(' ', "");
+        assert!(code.contains("pubfnnew(name:String)->Self{"));
+        assert!(code.contains(
+            "Self{name,age:None,kind:Default::default(),additional_properties:Default::default()}"
+        ));
+    }
+
+    #[test]
+    fn test_generate_encapsulate() {
+        let mut properties = IndexMap::new();
+        for (name, field_type) in [
+            ("name", FieldType::Simple(Primitive::String)),
+            (
+                "kind",
+                FieldType::Const(Primitive::String, "user".to_string()),
+            ),
+        ] {
+            properties.insert(
+                name.to_string(),
+                Field {
+                    optional: false,
+                    field_type,
+                    constraints: vec![],
+                    write_only: false,
+                    extensions: Default::default(),
+                },
+            );
+        }
+        let entity = Entity {
+            name: "User".to_string(),
+            def: EntityDef::Struct(StructDef {
+                properties,
+                additional_properties: None,
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+        };
+        let config = GeneratorConfig {
+            encapsulate: true,
+            ..Default::default()
+        };
+        let code = generate_entity(entity, &config, &Default::default()).replace(' ', "");
+        assert!(code.contains("pubstructUser{"));
+        assert!(!code.contains("pubname:"));
+        assert!(code.contains("pubfnname(&self)->&String{&self.name}"));
+        assert!(code.contains("pubfnname_mut(&mutself)->&mutString{&mutself.name}"));
+        assert!(code.contains("pubfnkind(&self)"));
+        assert!(!code.contains("kind_mut"));
+    }
+
+    #[test]
+    fn test_generate_redact_write_only() {
+        let mut properties = IndexMap::new();
+        for (name, write_only) in [("username", false), ("password", true)] {
+            properties.insert(
+                name.to_string(),
+                Field {
+                    optional: false,
+                    field_type: FieldType::Simple(Primitive::String),
+                    constraints: vec![],
+                    write_only,
+                    extensions: Default::default(),
+                },
+            );
+        }
+        let entity = Entity {
+            name: "Login".to_string(),
+            def: EntityDef::Struct(StructDef {
+                properties,
+                additional_properties: None,
+                example: None,
+                dependent_required: IndexMap::new(),
+                display: None,
+            }),
+        };
+        let config = GeneratorConfig {
+            redact_write_only: true,
+            ..Default::default()
+        };
+        let code = generate_entity(entity.clone(), &config, &Default::default()).replace(' ', "");
+        assert!(code.contains("#[derive(Clone,Eq,PartialEq,"));
+        assert!(code.contains("implstd::fmt::DebugforLogin"));
+        assert!(code.contains(".field(\"username\",&self.username)"));
+        assert!(code.contains(".field(\"password\",&\"[redacted]\")"));
+        let code = generate_entity(entity, &GeneratorConfig::default(), &Default::default())
+            .replace(' ', "");
+        assert!(code.contains("#[derive(Debug,Clone,"));
+        assert!(!code.contains("[redacted]"));
+    }
+
+    #[test]
+    fn test_generate_example() {
+        let mut address_properties = IndexMap::new();
+        address_properties.insert(
+            "street".to_string(),
+            Field {
+                optional: false,
+                field_type: FieldType::Simple(Primitive::String),
+                constraints: vec![],
+                write_only: false,
+                extensions: Default::default(),
+            },
+        );
+        let mut user_properties = IndexMap::new();
+        for (name, field_type, optional) in [
+            ("userId", FieldType::Simple(Primitive::Long), false),
+            ("nickname", FieldType::Simple(Primitive::String), true),
+            ("address", FieldType::Named("Address".to_string()), false),
+            (
+                "scores",
+                FieldType::Array(Some(Box::new(FieldType::Simple(Primitive::Int)))),
+                true,
+            ),
+        ] {
+            user_properties.insert(
+                name.to_string(),
+                Field {
+                    optional,
+                    field_type,
+                    constraints: vec![],
+                    write_only: false,
+                    extensions: Default::default(),
+                },
+            );
+        }
+        let entities = vec![
+            Entity {
+                name: "Address".to_string(),
+                def: EntityDef::Struct(StructDef {
+                    properties: address_properties,
+                    additional_properties: None,
+                    example: None,
+                    dependent_required: IndexMap::new(),
+                    display: None,
+                }),
+            },
+            Entity {
+                name: "User".to_string(),
+                def: EntityDef::Struct(StructDef {
+                    properties: user_properties,
+                    additional_properties: None,
+                    example: Some(serde_json::json!({
+                        "userId": -3,
+                        "address": {"street": "Main"},
+                        "scores": [1, 2]
+                    })),
+                    dependent_required: IndexMap::new(),
+                    display: None,
+                }),
+            },
+        ];
+        let code = generate_code(entities, &GeneratorConfig::default()).replace(' ', "");
+        assert!(code.contains(
+            "pubfnexample()->Self{User{user_id:-3i64,nickname:None,\
+             address:Address{street:\"Main\".to_string()},scores:Some(vec![1i32,2i32])}}"
+        ));
+        assert_eq!(code.matches("fnexample").count(), 1);
     }
 }
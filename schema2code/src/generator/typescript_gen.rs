@@ -0,0 +1,229 @@
+use crate::{
+    map_items,
+    parser::{Entity, EntityDef, EnumDef, FieldType, Primitive, StructDef},
+};
+
+pub fn generate_code(entities: Vec<Entity>, parallel: bool) -> String {
+    let code = map_items(entities, parallel, generate_entity);
+    format!("{}\n", code.join("\n\n"))
+}
+
+/// A JSON string literal is also a valid TypeScript one
+fn string_literal(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+/// Property names that aren't identifiers, e.g. `content-type`, have to be quoted
+fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        string_literal(name)
+    }
+}
+
+fn expand_field_type(field_type: FieldType) -> String {
+    match field_type {
+        FieldType::Named(t) => t,
+        FieldType::Array(Some(item_type)) => format!("Array<{}>", expand_field_type(*item_type)),
+        FieldType::Array(None) => "Array<unknown>".into(),
+        FieldType::Object(Some(value_type)) => {
+            format!("Record<string, {}>", expand_field_type(*value_type))
+        }
+        FieldType::Object(None) => "Record<string, unknown>".into(),
+        FieldType::Tuple(tuple_types) => {
+            let tuple_types = tuple_types
+                .into_iter()
+                .map(expand_field_type)
+                .collect::<Vec<_>>();
+            format!("[{}]", tuple_types.join(", "))
+        }
+        FieldType::Boxed(inner) => expand_field_type(*inner),
+        FieldType::Simple(primitive) => match primitive {
+            Primitive::I8
+            | Primitive::I16
+            | Primitive::Int
+            | Primitive::Long
+            | Primitive::I128
+            | Primitive::U8
+            | Primitive::U16
+            | Primitive::U32
+            | Primitive::U64
+            | Primitive::U128
+            | Primitive::Float
+            | Primitive::Double => "number".into(),
+            // bytes are base64 and decimals are serialized as strings to keep their precision
+            Primitive::String | Primitive::Uuid | Primitive::Bytes | Primitive::Decimal => {
+                "string".into()
+            }
+            Primitive::Bool => "boolean".into(),
+        },
+        FieldType::Const(primitive, value) => match primitive {
+            Primitive::String | Primitive::Uuid | Primitive::Bytes | Primitive::Decimal => {
+                string_literal(&value)
+            }
+            _ => value,
+        },
+    }
+}
+
+fn generate_entity(entity: Entity) -> String {
+    let identifier = entity.name;
+    match entity.def {
+        EntityDef::Struct(StructDef {
+            properties,
+            additional_properties,
+            ..
+        }) => {
+            let mut members = properties
+                .into_iter()
+                .map(|(name, field)| {
+                    let optional = if field.optional { "?" } else { "" };
+                    format!(
+                        "  {}{}: {};",
+                        property_name(&name),
+                        optional,
+                        expand_field_type(field.field_type)
+                    )
+                })
+                .collect::<Vec<_>>();
+            // an index signature has to fit every property, so the type of the additional ones
+            // can't be narrowed any further
+            if additional_properties.is_some() {
+                members.push("  [key: string]: unknown;".into());
+            }
+            if members.is_empty() {
+                format!("export interface {identifier} {{}}")
+            } else {
+                format!(
+                    "export interface {identifier} {{\n{}\n}}",
+                    members.join("\n")
+                )
+            }
+        }
+        EntityDef::OneOf {
+            discriminant,
+            variants,
+            tags,
+        } => {
+            let variants = variants
+                .into_iter()
+                .map(|variant| match &discriminant {
+                    Some(discriminant) => {
+                        let tag = tags.get(&variant).unwrap_or(&variant);
+                        format!(
+                            "({{ {}: {} }} & {})",
+                            property_name(discriminant),
+                            string_literal(tag),
+                            variant
+                        )
+                    }
+                    None => variant,
+                })
+                .collect::<Vec<_>>();
+            format!("export type {identifier} = {};", union(variants))
+        }
+        EntityDef::AllOf(members) => {
+            format!("export type {identifier} = {};", members.join(" & "))
+        }
+        EntityDef::Enum(EnumDef { values, repr, .. }) => {
+            // integer values are their own literal
+            let values = values
+                .iter()
+                .map(|value| match repr {
+                    Some(_) => value.clone(),
+                    None => string_literal(value),
+                })
+                .collect();
+            format!("export type {identifier} = {};", union(values))
+        }
+        EntityDef::Tuple(members, tail) => {
+            let mut members = members
+                .into_iter()
+                .map(expand_field_type)
+                .collect::<Vec<_>>();
+            if let Some(tail) = tail {
+                members.push(format!("...{}[]", expand_field_type(tail)));
+            }
+            format!("export type {identifier} = [{}];", members.join(", "))
+        }
+        EntityDef::MixedEnum(members) => {
+            let members = members.into_iter().map(expand_field_type).collect();
+            format!("export type {identifier} = {};", union(members))
+        }
+        EntityDef::Map(value_type, _) => {
+            let value_type = expand_field_type(FieldType::Object(value_type.map(Box::new)));
+            format!("export type {identifier} = {value_type};")
+        }
+        EntityDef::Alias(target) => {
+            format!("export type {identifier} = {};", expand_field_type(target))
+        }
+    }
+}
+
+/// `never` is the union of no types at all
+fn union(members: Vec<String>) -> String {
+    if members.is_empty() {
+        "never".into()
+    } else {
+        members.join(" | ")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Anonymous entities are numbered in the order they happen to be parsed in, so the
+    /// declarations are compared without the numbers and regardless of their order
+    fn normalized_declarations(code: &str) -> Vec<String> {
+        let mut declarations = code
+            .trim()
+            .split("\n\n")
+            .map(|declaration| {
+                let mut parts = declaration.split("AnonymousEntity");
+                let first = parts.next().unwrap_or_default().to_string();
+                parts.fold(first, |out, part| {
+                    format!(
+                        "{out}AnonymousEntity{}",
+                        part.trim_start_matches(|c: char| c.is_ascii_digit())
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        declarations.sort();
+        declarations
+    }
+
+    #[test]
+    fn test_resources_asyncapi_snapshot() {
+        let entities = crate::parse_spec(include_str!("../../resources/asyncapi.yaml")).unwrap();
+        let code = generate_code(entities, true);
+        assert_eq!(
+            normalized_declarations(&code),
+            normalized_declarations(include_str!("../../resources/asyncapi.ts"))
+        );
+    }
+
+    #[test]
+    fn test_generate_discriminated_union() {
+        let entity = Entity {
+            name: "Shape".to_string(),
+            def: EntityDef::OneOf {
+                discriminant: Some("kind".to_string()),
+                variants: vec!["Circle".to_string(), "Square".to_string()],
+                tags: [("Square".to_string(), "square".to_string())].into(),
+            },
+        };
+        assert_eq!(
+            generate_entity(entity),
+            r#"export type Shape = ({ kind: "Circle" } & Circle) | ({ kind: "square" } & Square);"#
+        );
+    }
+}
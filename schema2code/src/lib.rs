@@ -1,11 +1,2420 @@
-use deserializer::SchemaDef;
-use std::collections::HashMap;
+#[cfg(any(test, feature = "compile-check"))]
+pub use compile_check::{check_compiles, check_runs};
+use deserializer::{MessageDef, SchemaDef};
+pub use error::GenerationError;
+pub use generator::{GeneratorConfig, MapType, StringType, TargetLanguage};
+use parser::{Entity, EntityDef};
+pub use resolver::ResolverConfig;
+use serde::de::DeserializeOwned;
+use std::{collections::HashMap, path::Path};
+#[cfg(feature = "wasm")]
+pub use wasm::generate_rust_wasm;
 
+#[cfg(any(test, feature = "compile-check"))]
+mod compile_check;
 pub mod deserializer;
-mod generator;
-pub(crate) mod parser;
+mod error;
+pub mod generator;
+pub mod parser;
+mod resolver;
+#[cfg(feature = "wasm")]
+mod wasm;
 
-pub fn generate_rust(input: HashMap<String, SchemaDef>) -> String {
-    let entities = parser::parse_schema_def_collection(input);
-    generator::generate_rust(entities)
+/// Generates Rust code for the `components -> schemas` of an asyncapi document
+pub fn generate_rust(input: &str) -> Result<String, GenerationError> {
+    generate_rust_with(input, &GeneratorConfig::default())
+}
+
+/// Like `generate_rust` but with the options of `config` instead of the defaults
+pub fn generate_rust_with(
+    input: &str,
+    config: &GeneratorConfig,
+) -> Result<String, GenerationError> {
+    let entities = parse_spec_with(input, COMPONENTS_POINTER, &resolver_config(config))?;
+    generator::check_rust(&entities, config)?;
+    Ok(generator::generate_rust(entities, config))
+}
+
+/// Like `generate_rust` but hands the parsed entities to `transform` before generating, to rename,
+/// add or filter types. `parser::rename_entities` renames types along with the references to them.
+pub fn generate_rust_with_transform(
+    input: &str,
+    transform: impl FnMut(&mut Vec<Entity>),
+) -> Result<String, GenerationError> {
+    generate_rust_with_transform_config(input, &GeneratorConfig::default(), transform)
+}
+
+/// Like `generate_rust_with_transform` but with the options of `config` instead of the defaults
+pub fn generate_rust_with_transform_config(
+    input: &str,
+    config: &GeneratorConfig,
+    mut transform: impl FnMut(&mut Vec<Entity>),
+) -> Result<String, GenerationError> {
+    let mut entities = parse_spec_with(input, COMPONENTS_POINTER, &resolver_config(config))?;
+    transform(&mut entities);
+    generator::check_rust(&entities, config)?;
+    Ok(generator::generate_rust(entities, config))
+}
+
+/// Like `generate_rust` but also returns where every generated type comes from, as pairs of the
+/// JSON pointer of its schema and the type name, e.g.
+/// `("#/components/schemas/GetUser/oneOf/1", "AnonymousEntity2")`
+pub fn generate_rust_with_report(
+    input: &str,
+) -> Result<(String, Vec<(String, String)>), GenerationError> {
+    generate_rust_with_report_config(input, &GeneratorConfig::default())
+}
+
+/// Like `generate_rust_with_report` but with the options of `config` instead of the defaults
+pub fn generate_rust_with_report_config(
+    input: &str,
+    config: &GeneratorConfig,
+) -> Result<(String, Vec<(String, String)>), GenerationError> {
+    let document = read_document(input)?;
+    let roots = resolver::resolve_pointer(&document, COMPONENTS_POINTER)
+        .and_then(serde_yaml::Value::as_mapping)
+        .map(|schemas| {
+            schemas
+                .keys()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let entities = parse_document(&document, COMPONENTS_POINTER, &resolver_config(config))?;
+    generator::check_rust(&entities, config)?;
+    let report = parser::entity_sources(&entities, &roots, COMPONENTS_POINTER);
+    let code = generator::generate_rust(entities, config);
+    Ok((code, report))
+}
+
+/// The `ResolverConfig` that goes with the options of `config`
+fn resolver_config(config: &GeneratorConfig) -> ResolverConfig {
+    ResolverConfig {
+        parallel: config.parallel,
+        strict_required: config.strict_required,
+        ..Default::default()
+    }
+}
+
+/// Where the schemas live in an asyncapi document
+pub const COMPONENTS_POINTER: &str = "/components/schemas";
+
+/// Parses the `components -> schemas` of an asyncapi document into the `Entity` representation
+/// that the generators consume, useful to inspect or transform the model before generating code.
+pub fn parse_spec(input: &str) -> Result<Vec<Entity>, GenerationError> {
+    parse_spec_at(input, COMPONENTS_POINTER)
+}
+
+/// Like `parse_spec` but reads the schemas from the mapping at the JSON pointer
+/// `components_pointer`, e.g. `/definitions` for plain JSON schema documents
+pub fn parse_spec_at(
+    input: &str,
+    components_pointer: &str,
+) -> Result<Vec<Entity>, GenerationError> {
+    parse_spec_with(input, components_pointer, &ResolverConfig::default())
+}
+
+/// Like `parse_spec_at` but resolves `$ref`s to other documents according to `config`
+pub fn parse_spec_with(
+    input: &str,
+    components_pointer: &str,
+    config: &ResolverConfig,
+) -> Result<Vec<Entity>, GenerationError> {
+    parse_document(&read_document(input)?, components_pointer, config)
+}
+
+fn parse_document(
+    document: &serde_yaml::Value,
+    components_pointer: &str,
+    config: &ResolverConfig,
+) -> Result<Vec<Entity>, GenerationError> {
+    let section = resolver::resolve_pointer(document, components_pointer)
+        .ok_or_else(|| GenerationError::PointerNotFound(components_pointer.to_string()))?;
+    let mut schemas = read_section::<SchemaDef>(section)?;
+    let ctx = resolver::resolve_refs(
+        document,
+        &[section],
+        components_pointer,
+        &mut schemas,
+        config,
+    )?;
+    if config.strict_required {
+        parser::check_required(&schemas)?;
+    }
+    parser::parse_schema_def_collection(schemas, &ctx)
+}
+
+/// Checks that all of the `components -> schemas` of an asyncapi document can be generated
+/// without generating anything. Every schema that can't be read or has a shape the parser
+/// doesn't support is reported, not only the first one.
+pub fn validate_spec(input: &str) -> Result<(), Vec<GenerationError>> {
+    validate_spec_at(input, COMPONENTS_POINTER)
+}
+
+/// Like `validate_spec` but for the schemas in the mapping at the JSON pointer
+/// `components_pointer`, see `parse_spec_at`
+pub fn validate_spec_at(input: &str, components_pointer: &str) -> Result<(), Vec<GenerationError>> {
+    let document = read_document(input).map_err(|e| vec![e])?;
+    let section = resolver::resolve_pointer(&document, components_pointer).ok_or_else(|| {
+        vec![GenerationError::PointerNotFound(
+            components_pointer.to_string(),
+        )]
+    })?;
+    let mut schemas = HashMap::new();
+    let mut errors = vec![];
+    for (name, value) in section.as_mapping().into_iter().flatten() {
+        let name = name.as_str().unwrap_or_default().to_string();
+        match serde_yaml::from_value::<SchemaDef>(value.clone()) {
+            Ok(schema) => {
+                schemas.insert(name, schema);
+            }
+            Err(e) => errors.push(GenerationError::InvalidSchema(name, e)),
+        }
+    }
+    // the schemas are still parsed without the refs, those that don't depend on them get checked
+    let ctx = resolver::resolve_refs(
+        &document,
+        &[section],
+        components_pointer,
+        &mut schemas,
+        &ResolverConfig::default(),
+    )
+    .unwrap_or_else(|e| {
+        errors.push(e);
+        Default::default()
+    });
+    if let Err(parse_errors) = parser::parse_schema_defs(schemas, &ctx) {
+        errors.extend(parse_errors);
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Generates the `components -> schemas` of an asyncapi document together with a type for every
+/// message payload in `components -> messages`, named after the message.
+pub fn generate_rust_from_messages(input: &str) -> Result<String, GenerationError> {
+    generate_rust_from_messages_with(input, &GeneratorConfig::default())
+}
+
+/// Like `generate_rust_from_messages` but with the options of `config` instead of the defaults
+pub fn generate_rust_from_messages_with(
+    input: &str,
+    config: &GeneratorConfig,
+) -> Result<String, GenerationError> {
+    let document = read_document(input)?;
+    let section = &document["components"]["schemas"];
+    let mut schemas = read_section::<SchemaDef>(section)?;
+    let message_section = &document["components"]["messages"];
+    let messages = read_section::<MessageDef>(message_section)?;
+    let ctx = resolver::resolve_refs(
+        &document,
+        &[section, message_section],
+        COMPONENTS_POINTER,
+        &mut schemas,
+        &resolver_config(config),
+    )?;
+    let mut payloads = messages
+        .iter()
+        .filter(|(_, message)| message.payload.is_some())
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    payloads.sort();
+    if config.strict_required {
+        parser::check_required(&schemas)?;
+        parser::check_required_messages(&messages)?;
+    }
+    let mut entities = parser::parse_schema_def_collection(schemas, &ctx)?;
+    let mut message_entities = parser::parse_message_collection(messages, &entities, &ctx)?;
+    entities.append(&mut message_entities);
+    if let Some(name) = &config.message_enum {
+        entities.push(Entity {
+            name: name.clone(),
+            def: EntityDef::OneOf {
+                discriminant: None,
+                variants: payloads,
+                tags: HashMap::new(),
+            },
+        });
+    }
+    generator::check_rust(&entities, config)?;
+    Ok(generator::generate_rust(entities, config))
+}
+
+/// Generates the `components -> schemas` of an asyncapi 3 document together with a type for every
+/// message payload reachable through `channels` and `operations`, named after the channel (or
+/// operation) followed by the message, e.g. `UserSignedUpPayload` for the message `payload` of the
+/// channel `user/signed-up`.
+pub fn generate_rust_from_channels(input: &str) -> Result<String, GenerationError> {
+    generate_rust_from_channels_with(input, &GeneratorConfig::default())
+}
+
+/// Like `generate_rust_from_channels` but with the options of `config` instead of the defaults
+pub fn generate_rust_from_channels_with(
+    input: &str,
+    config: &GeneratorConfig,
+) -> Result<String, GenerationError> {
+    let document = read_document(input)?;
+    let section = &document["components"]["schemas"];
+    let mut schemas = read_section::<SchemaDef>(section)?;
+    let channel_messages = resolver::collect_channel_messages(&document)?;
+    let messages = read_section::<MessageDef>(&channel_messages)?;
+    let ctx = resolver::resolve_refs(
+        &document,
+        &[section, &channel_messages],
+        COMPONENTS_POINTER,
+        &mut schemas,
+        &resolver_config(config),
+    )?;
+    if config.strict_required {
+        parser::check_required(&schemas)?;
+        parser::check_required_messages(&messages)?;
+    }
+    let mut entities = parser::parse_schema_def_collection(schemas, &ctx)?;
+    let mut message_entities = parser::parse_message_collection(messages, &entities, &ctx)?;
+    entities.append(&mut message_entities);
+    generator::check_rust(&entities, config)?;
+    Ok(generator::generate_rust(entities, config))
+}
+
+/// Generates the schemas of every `.yaml`, `.yml` and `.json` file in `dir` as if they were the
+/// `components -> schemas` of a single document, so `$ref`s between them point there. A file
+/// with `components -> schemas` contributes all of those, any other file is a single schema named
+/// after the file, e.g. `user-profile.yaml` -> `UserProfile`.
+pub fn generate_rust_from_dir(dir: &Path) -> Result<String, GenerationError> {
+    generate_rust_from_dir_with(dir, &GeneratorConfig::default())
+}
+
+/// Like `generate_rust_from_dir` but with the options of `config` instead of the defaults
+pub fn generate_rust_from_dir_with(
+    dir: &Path,
+    config: &GeneratorConfig,
+) -> Result<String, GenerationError> {
+    let io_error = |path: &Path| {
+        let path = path.display().to_string();
+        move |e| GenerationError::Io(path, e)
+    };
+    let mut paths = std::fs::read_dir(dir)
+        .map_err(io_error(dir))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error(dir))?;
+    paths.retain(|path| {
+        path.is_file()
+            && path
+                .extension()
+                .is_some_and(|extension| ["yaml", "yml", "json"].iter().any(|e| extension == *e))
+    });
+    paths.sort();
+    let mut schemas = serde_yaml::Mapping::new();
+    for path in paths {
+        let input = std::fs::read_to_string(&path).map_err(io_error(&path))?;
+        let document = read_document(&input)?;
+        let file_schemas = match document["components"]["schemas"].as_mapping() {
+            Some(file_schemas) => file_schemas.clone(),
+            None => {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                [(parser::pascal_case(&stem).into(), document)]
+                    .into_iter()
+                    .collect()
+            }
+        };
+        for (name, schema) in file_schemas {
+            if schemas.contains_key(&name) {
+                let name = name.as_str().unwrap_or_default().to_string();
+                return Err(GenerationError::DuplicateSchema(name));
+            }
+            schemas.insert(name, schema);
+        }
+    }
+    let mut document = serde_yaml::Mapping::new();
+    let mut components = serde_yaml::Mapping::new();
+    components.insert("schemas".into(), schemas.into());
+    document.insert("components".into(), components.into());
+    let entities = parse_document(
+        &document.into(),
+        COMPONENTS_POINTER,
+        &resolver_config(config),
+    )?;
+    generator::check_rust(&entities, config)?;
+    Ok(generator::generate_rust(entities, config))
+}
+
+/// Parses the yaml document and resolves merge keys (`<<: *anchor`), serde_yaml only expands the
+/// aliases so the anchored mapping would otherwise end up under a literal `<<` key. Draft-07
+/// `items` tuples are rewritten to `prefixItems`.
+fn read_document(input: &str) -> Result<serde_yaml::Value, GenerationError> {
+    let mut document = serde_yaml::from_str::<serde_yaml::Value>(input)?;
+    document.apply_merge()?;
+    deserializer::normalize_tuple_items(&mut document);
+    Ok(document)
+}
+
+/// Reads a mapping section of the document, a missing section is treated as empty.
+/// Every entry is read on its own so that an error names the one that doesn't fit.
+fn read_section<T: DeserializeOwned>(
+    section: &serde_yaml::Value,
+) -> Result<HashMap<String, T>, GenerationError> {
+    if section.is_null() {
+        return Ok(HashMap::new());
+    }
+    let Some(mapping) = section.as_mapping() else {
+        return Ok(serde_yaml::from_value(section.clone())?);
+    };
+    mapping
+        .iter()
+        .map(|(name, value)| {
+            let name = serde_yaml::from_value::<String>(name.clone())?;
+            match serde_yaml::from_value(value.clone()) {
+                Ok(value) => Ok((name, value)),
+                Err(e) => Err(GenerationError::InvalidSchema(name, e)),
+            }
+        })
+        .collect()
+}
+
+/// Maps every item with `f` on the rayon thread pool, or one after the other on the current
+/// thread when `parallel` is off. The results are in the order of `items` either way.
+#[cfg(feature = "parallel")]
+pub(crate) fn map_items<I, T, R>(
+    items: I,
+    parallel: bool,
+    f: impl Fn(T) -> R + Sync + Send,
+) -> Vec<R>
+where
+    I: IntoIterator<Item = T> + rayon::iter::IntoParallelIterator<Item = T>,
+    R: Send,
+{
+    use rayon::iter::ParallelIterator;
+    if parallel {
+        items.into_par_iter().map(f).collect()
+    } else {
+        items.into_iter().map(f).collect()
+    }
+}
+
+/// Without the `parallel` feature everything runs on the current thread, e.g. in wasm
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map_items<I, T, R>(items: I, _parallel: bool, f: impl Fn(T) -> R) -> Vec<R>
+where
+    I: IntoIterator<Item = T>,
+{
+    items.into_iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::{EntityDef, FieldType, StructDef};
+
+    #[test]
+    fn test_generate_from_messages() {
+        let yaml = r#"
+            components:
+              messages:
+                UserCreated:
+                  payload:
+                    type: object
+                    properties:
+                      userId:
+                        type: string
+                    required:
+                      - userId
+                UserDeleted:
+                  payload:
+                    $ref: '#/components/schemas/UserRef'
+              schemas:
+                UserRef:
+                  type: object
+                  properties:
+                    userId:
+                      type: string
+        "#;
+        let code = generate_rust_from_messages(yaml).unwrap();
+        check_compiles(&code).unwrap();
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubstructUserCreated{"));
+        assert!(code.contains("pubuser_id:String"));
+        assert!(code.contains("pubtypeUserDeleted=UserRef;"));
+        assert!(code.contains("pubstructUserRef{"));
+    }
+
+    #[test]
+    fn test_message_payload_not_an_object() {
+        let yaml = r#"
+            components:
+              messages:
+                UserList:
+                  payload:
+                    type: array
+                    items:
+                      $ref: '#/components/schemas/User'
+                Renamed:
+                  payload:
+                    type: array
+                    items:
+                      type: object
+                      properties:
+                        from:
+                          type: string
+                Greeting:
+                  payload:
+                    type: string
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+        "#;
+        let code = generate_rust_from_messages(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubtypeUserList=Vec<User>;"));
+        assert!(code.contains("pubtypeRenamed=Vec<RenamedItem>;"));
+        assert!(code.contains("pubstructRenamedItem{"));
+        assert!(code.contains("pubtypeGreeting=String;"));
+    }
+
+    #[test]
+    fn test_message_anonymous_entities_deterministic() {
+        let yaml = r#"
+            components:
+              messages:
+                Created:
+                  payload:
+                    oneOf:
+                      - type: object
+                        properties:
+                          id:
+                            type: string
+                      - type: object
+                        properties:
+                          ids:
+                            type: array
+                            items:
+                              type: string
+                Deleted:
+                  payload:
+                    oneOf:
+                      - type: object
+                        properties:
+                          id:
+                            type: integer
+                      - type: object
+                        properties:
+                          reason:
+                            type: string
+              schemas:
+                Shape:
+                  oneOf:
+                    - type: object
+                      properties:
+                        radius:
+                          type: number
+                    - type: object
+                      properties:
+                        side:
+                          type: number
+        "#;
+        let code = generate_rust_from_messages(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        for _ in 0..10 {
+            assert_eq!(generate_rust_from_messages(yaml).unwrap(), code);
+        }
+        // numbered on from the anonymous entities of the schemas, in the order of the messages
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubenumShape{AnonymousEntity1(AnonymousEntity1),"));
+        assert!(code.contains("pubenumCreated{AnonymousEntity3(AnonymousEntity3),"));
+        assert!(code.contains("pubenumDeleted{AnonymousEntity5(AnonymousEntity5),"));
+    }
+
+    #[test]
+    fn test_message_payload_ref_outside_schemas() {
+        let yaml = r#"
+            definitions:
+              Tag:
+                type: object
+                properties:
+                  label:
+                    type: string
+            components:
+              messages:
+                Tagged:
+                  payload:
+                    type: object
+                    properties:
+                      tag:
+                        $ref: '#/definitions/Tag'
+                Broken:
+                  payload:
+                    type: object
+                    properties:
+                      other:
+                        $ref: '#/definitions/Missing'
+        "#;
+        match generate_rust_from_messages(yaml) {
+            Err(GenerationError::UnresolvedRef(pointer)) => {
+                assert_eq!(pointer, "#/definitions/Missing")
+            }
+            other => panic!("Expected an UnresolvedRef error, got {:?}", other),
+        }
+        let yaml = yaml.split("                Broken:").next().unwrap();
+        let code = generate_rust_from_messages(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubstructTag{"));
+        assert!(code.contains("pubtag:Option<Tag>"));
+    }
+
+    #[test]
+    fn test_generate_message_enum() {
+        let yaml = r#"
+            components:
+              messages:
+                Ping:
+                  payload:
+                    type: object
+                    properties:
+                      sentAt:
+                        type: integer
+                Heartbeat: {}
+                Alert:
+                  payload:
+                    $ref: '#/components/schemas/Alert'
+              schemas:
+                Alert:
+                  type: object
+                  properties:
+                    level:
+                      type: string
+        "#;
+        let config = GeneratorConfig {
+            message_enum: Some("Messages".to_string()),
+            ..Default::default()
+        };
+        let code = generate_rust_from_messages_with(yaml, &config).unwrap();
+        check_compiles(&code).unwrap();
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubenumMessages{Alert(Alert),Ping(Ping)}"));
+        assert!(code.contains("pubfnfrom_json(json:&str)->serde_json::Result<Self>"));
+        assert!(!generate_rust_from_messages(yaml)
+            .unwrap()
+            .contains("Messages"));
+    }
+
+    #[test]
+    fn test_generate_from_channels() {
+        let yaml = r#"
+            asyncapi: 3.0.0
+            channels:
+              user/signed-up:
+                address: user/signed-up
+                messages:
+                  created:
+                    payload:
+                      type: object
+                      properties:
+                        userId:
+                          type: string
+                  deleted:
+                    $ref: '#/components/messages/UserDeleted'
+            operations:
+              onUserSignedUp:
+                action: receive
+                channel:
+                  $ref: '#/channels/user~1signed-up'
+                messages:
+                  - $ref: '#/channels/user~1signed-up/messages/created'
+              sendAudit:
+                action: send
+                messages:
+                  - name: audit
+                    payload:
+                      type: object
+                      properties:
+                        entry:
+                          type: string
+                  - name: tags
+                    payload:
+                      type: array
+                      items:
+                        type: string
+            components:
+              messages:
+                UserDeleted:
+                  payload:
+                    $ref: '#/components/schemas/UserRef'
+              schemas:
+                UserRef:
+                  type: object
+                  properties:
+                    userId:
+                      type: string
+        "#;
+        let code = generate_rust_from_channels(yaml).unwrap();
+        check_compiles(&code).unwrap();
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubstructUserSignedUpCreated{"));
+        assert!(code.contains("pubtypeUserSignedUpDeleted=UserRef;"));
+        assert!(code.contains("pubstructSendAuditAudit{"));
+        assert!(code.contains("pubtypeSendAuditTags=Vec<String>;"));
+        assert!(code.contains("pubstructUserRef{"));
+        assert!(!code.contains("OnUserSignedUp"));
+
+        let config = GeneratorConfig {
+            deny_unknown_fields: true,
+            ..Default::default()
+        };
+        let code = generate_rust_from_channels_with(yaml, &config).unwrap();
+        assert!(code
+            .replace(' ', "")
+            .contains("#[serde(deny_unknown_fields)]pubstructUserSignedUpCreated{"));
+    }
+
+    #[test]
+    fn test_generate_rust_with_config() {
+        let yaml = r#"
+            components:
+              schemas:
+                Account:
+                  type: object
+                  properties:
+                    status:
+                      title: Status
+                      type: string
+                      enum:
+                        - active
+                        - inactive
+                    note:
+                      type: string
+        "#;
+        let config = GeneratorConfig {
+            unknown_enum_variant: true,
+            deny_unknown_fields: true,
+            skip_serializing_none: false,
+            visibility: "pub(crate)".to_string(),
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap();
+        check_compiles(&code).unwrap();
+        let code = code.replace(' ', "");
+        assert!(code.contains("#[serde(deny_unknown_fields)]"));
+        assert!(code.contains("Unknown(String)"));
+        assert!(code.contains("pub(crate)structAccount{"));
+        assert!(!code.contains("skip_serializing_if"));
+    }
+
+    #[test]
+    fn test_unknown_enum_variant_named_unknown() {
+        let yaml = r#"
+            components:
+              schemas:
+                Status:
+                  enum: [Unknown, known]
+        "#;
+        let config = GeneratorConfig {
+            unknown_enum_variant: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("Unknown0,Known,Unknown(String)"));
+        assert!(code.contains("\"Unknown\"=>Self::Unknown0"));
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let yaml = r#"
+            components:
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                  required:
+                    - name
+        "#;
+        let entities = parse_spec(yaml).unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].name, "User");
+        match &entities[0].def {
+            EntityDef::Struct(StructDef { properties, .. }) => {
+                let name = properties.get("name").unwrap();
+                assert!(!name.optional);
+                assert!(matches!(name.field_type, FieldType::Simple(_)));
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+        let code = generator::generate_rust(entities, &GeneratorConfig::default());
+        assert!(code.contains("pub struct User"));
+    }
+
+    #[test]
+    fn test_parse_spec_at() {
+        let yaml = r#"
+            definitions:
+              User:
+                type: object
+                properties:
+                  name:
+                    type: string
+        "#;
+        let entities = parse_spec_at(yaml, "/definitions").unwrap();
+        assert_eq!(entities[0].name, "User");
+        assert!(matches!(
+            parse_spec_at(yaml, "/components/schemas"),
+            Err(GenerationError::PointerNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_nested_property_ref() {
+        let yaml = r#"
+            components:
+              schemas:
+                Foo:
+                  type: object
+                  properties:
+                    bar:
+                      type: string
+                Copy:
+                  type: object
+                  properties:
+                    bar:
+                      $ref: '#/components/schemas/Foo/properties/bar'
+        "#;
+        let entities = parse_spec(yaml).unwrap();
+        let copy = entities
+            .iter()
+            .find(|entity| entity.name == "Copy")
+            .unwrap();
+        match &copy.def {
+            EntityDef::Struct(StructDef { properties, .. }) => assert!(matches!(
+                properties.get("bar").unwrap().field_type,
+                FieldType::Simple(crate::parser::Primitive::String)
+            )),
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_resolve_definitions_ref() {
+        let yaml = r#"
+            definitions:
+              Baz:
+                type: object
+                properties:
+                  id:
+                    type: string
+            components:
+              schemas:
+                Holder:
+                  type: object
+                  properties:
+                    baz:
+                      $ref: '#/definitions/Baz'
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap();
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubstructBaz{"));
+        assert!(code.contains("pubbaz:Option<Baz>"));
+    }
+
+    #[test]
+    fn test_inline_name_clash() {
+        let yaml = r#"
+            components:
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    address:
+                      type: object
+                      properties:
+                        street:
+                          type: string
+                    billing:
+                      $ref: '#/components/schemas/UserAddress'
+                UserAddress:
+                  type: object
+                  properties:
+                    id:
+                      type: string
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert_eq!(code.matches("pubstructUserAddress{").count(), 1);
+        assert!(code.contains("pubaddress:Option<AnonymousEntity1>"));
+        assert!(code.contains("pubbilling:Option<UserAddress>"));
+    }
+
+    #[test]
+    fn test_ref_name_collision() {
+        let yaml = r#"
+            definitions:
+              Baz:
+                type: object
+                properties:
+                  id:
+                    type: string
+            components:
+              schemas:
+                Baz:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                Holder:
+                  type: object
+                  properties:
+                    baz:
+                      $ref: '#/definitions/Baz'
+        "#;
+        match generate_rust(yaml) {
+            Err(GenerationError::DuplicateSchema(name)) => assert_eq!(name, "Baz"),
+            other => panic!("Expected a DuplicateSchema error, got {:?}", other),
+        }
+        let yaml = r#"
+            definitions:
+              Baz:
+                type: object
+                properties:
+                  id:
+                    type: string
+            $defs:
+              Baz:
+                type: object
+                properties:
+                  name:
+                    type: string
+            components:
+              schemas:
+                Holder:
+                  type: object
+                  properties:
+                    first:
+                      $ref: '#/definitions/Baz'
+                    second:
+                      $ref: '#/$defs/Baz'
+        "#;
+        match generate_rust(yaml) {
+            Err(GenerationError::DuplicateSchema(name)) => assert_eq!(name, "Baz"),
+            other => panic!("Expected a DuplicateSchema error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_root_ref() {
+        let yaml = r#"
+            components:
+              schemas:
+                Category:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                    parent:
+                      $ref: '#'
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap();
+        assert!(code
+            .replace(' ', "")
+            .contains("pubparent:Option<Box<Category>>"));
+    }
+
+    #[test]
+    fn test_merge_keys() {
+        let yaml = r#"
+            components:
+              schemas:
+                Entity:
+                  type: object
+                  properties: &entity_properties
+                    id:
+                      type: string
+                    createdAt:
+                      type: integer
+                  required: &entity_required
+                    - id
+                User:
+                  type: object
+                  properties:
+                    <<: *entity_properties
+                    name:
+                      type: string
+                  required: *entity_required
+        "#;
+        let entities = parse_spec(yaml).unwrap();
+        let user = entities
+            .iter()
+            .find(|entity| entity.name == "User")
+            .unwrap();
+        match &user.def {
+            EntityDef::Struct(StructDef { properties, .. }) => {
+                assert_eq!(properties.len(), 3);
+                assert!(!properties.get("id").unwrap().optional);
+                assert!(properties.contains_key("createdAt"));
+                assert!(properties.contains_key("name"));
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_additional_properties_ref() {
+        let yaml = r#"
+            components:
+              schemas:
+                Foo:
+                  type: object
+                  properties:
+                    id:
+                      type: string
+                Named:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                  additionalProperties:
+                    $ref: '#/components/schemas/Foo'
+                FooMap:
+                  type: object
+                  additionalProperties:
+                    $ref: '#/components/schemas/Foo'
+                FooIds:
+                  type: object
+                  additionalProperties:
+                    $ref: '#/components/schemas/Foo/properties/id'
+                Holder:
+                  type: object
+                  properties:
+                    foos:
+                      type: object
+                      additionalProperties:
+                        $ref: '#/components/schemas/Foo'
+                  required: [foos]
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap();
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubstructFoo{"));
+        assert!(code.contains("pubfoos:std::collections::HashMap<String,Foo>"));
+        assert!(!code.contains("HashMap<String,std::collections::HashMap"));
+        assert!(code.contains("pubadditional_properties:std::collections::HashMap<String,Foo>"));
+        assert!(code.contains("pubstructFooMap(pubstd::collections::HashMap<String,Foo>);"));
+        assert!(code.contains("pubstructFooIds(pubstd::collections::HashMap<String,String>);"));
+    }
+
+    #[test]
+    fn test_unresolved_ref() {
+        let yaml = r#"
+            components:
+              schemas:
+                Holder:
+                  type: object
+                  properties:
+                    missing:
+                      $ref: '#/components/schemas/Missing'
+        "#;
+        match parse_spec(yaml) {
+            Err(GenerationError::UnresolvedRef(pointer)) => {
+                assert_eq!(pointer, "#/components/schemas/Missing")
+            }
+            other => panic!("Expected an UnresolvedRef error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_discriminator_tags_from_consts() {
+        let yaml = r#"
+            components:
+              schemas:
+                Request:
+                  oneOf:
+                    - $ref: '#/components/schemas/GetUser'
+                    - $ref: '#/components/schemas/DeleteUser'
+                  discriminator:
+                    propertyName: event
+                GetUser:
+                  type: object
+                  properties:
+                    event:
+                      type: string
+                      const: getUser
+                    userId:
+                      type: string
+                  required:
+                    - event
+                DeleteUser:
+                  type: object
+                  properties:
+                    userId:
+                      type: string
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("#[serde(rename=\"getUser\")]GetUser(GetUser)"));
+        assert!(code.contains("Self::GetUser(_)=>\"getUser\""));
+        assert!(code.contains(",DeleteUser(DeleteUser)"));
+    }
+
+    #[test]
+    fn test_generate_rust_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("user-profile.yaml"),
+            r#"
+            type: object
+            properties:
+              name:
+                type: string
+            required:
+              - name
+            "#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("events.json"),
+            r##"{"components": {"schemas": {"UserCreated": {
+                "type": "object",
+                "properties": {"user": {"$ref": "#/components/schemas/UserProfile"}},
+                "required": ["user"]
+            }}}}"##,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a schema").unwrap();
+        let code = generate_rust_from_dir(dir.path()).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubstructUserProfile{"));
+        assert!(code.contains("pubuser:UserProfile"));
+        let config = GeneratorConfig {
+            deny_unknown_fields: true,
+            ..Default::default()
+        };
+        let code = generate_rust_from_dir_with(dir.path(), &config).unwrap();
+        assert!(code.contains("deny_unknown_fields"));
+
+        std::fs::write(
+            dir.path().join("more-events.yaml"),
+            "components:\n  schemas:\n    UserCreated:\n      type: object\n",
+        )
+        .unwrap();
+        match generate_rust_from_dir(dir.path()) {
+            Err(GenerationError::DuplicateSchema(name)) => assert_eq!(name, "UserCreated"),
+            other => panic!("Expected a DuplicateSchema error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_strict_required() {
+        let yaml = r#"
+            components:
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    address:
+                      type: object
+                      properties:
+                        city:
+                          type: string
+                      required:
+                        - ctiy
+                  required:
+                    - address
+        "#;
+        assert!(parse_spec(yaml).is_ok());
+        let config = ResolverConfig {
+            strict_required: true,
+            ..Default::default()
+        };
+        match parse_spec_with(yaml, COMPONENTS_POINTER, &config) {
+            Err(GenerationError::UnknownRequiredProperty(schema, property)) => {
+                assert_eq!(schema, "User/properties/address");
+                assert_eq!(property, "ctiy");
+            }
+            other => panic!("Expected an UnknownRequiredProperty error, got {:?}", other),
+        }
+
+        assert!(generate_rust(yaml).is_ok());
+        let config = GeneratorConfig {
+            strict_required: true,
+            ..Default::default()
+        };
+        match generate_rust_with(yaml, &config) {
+            Err(GenerationError::UnknownRequiredProperty(schema, property)) => {
+                assert_eq!(schema, "User/properties/address");
+                assert_eq!(property, "ctiy");
+            }
+            other => panic!("Expected an UnknownRequiredProperty error, got {:?}", other),
+        }
+        let yaml = r#"
+            components:
+              messages:
+                UserCreated:
+                  payload:
+                    type: object
+                    properties:
+                      id:
+                        type: string
+                    required:
+                      - di
+        "#;
+        assert!(generate_rust_from_messages(yaml).is_ok());
+        match generate_rust_from_messages_with(yaml, &config) {
+            Err(GenerationError::UnknownRequiredProperty(schema, property)) => {
+                assert_eq!(schema, "UserCreated");
+                assert_eq!(property, "di");
+            }
+            other => panic!("Expected an UnknownRequiredProperty error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_remote_ref_opt_in() {
+        let yaml = r#"
+            components:
+              schemas:
+                Holder:
+                  type: object
+                  properties:
+                    money:
+                      $ref: 'https://schemas.example.com/common.yaml#/components/schemas/Money'
+        "#;
+        assert!(matches!(
+            parse_spec(yaml),
+            Err(GenerationError::UnresolvedRef(_))
+        ));
+    }
+
+    #[cfg(feature = "remote-refs")]
+    #[test]
+    fn test_remote_ref() {
+        use std::io::{Read, Write};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let remote = r#"
+            components:
+              schemas:
+                Money:
+                  type: object
+                  properties:
+                    amount:
+                      type: integer
+                    currency:
+                      $ref: '#/components/schemas/Currency'
+                  required:
+                    - amount
+                Currency:
+                  type: string
+        "#;
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let served = requests.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                served.fetch_add(1, Ordering::SeqCst);
+                let mut request = [0; 1024];
+                let _ = stream.read(&mut request);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    remote.len(),
+                    remote
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        let yaml = format!(
+            r#"
+            components:
+              schemas:
+                Holder:
+                  type: object
+                  properties:
+                    price:
+                      $ref: 'http://{address}/common.yaml#/components/schemas/Money'
+                    refund:
+                      $ref: 'http://{address}/common.yaml#/components/schemas/Money'
+            "#
+        );
+        let config = ResolverConfig {
+            remote_refs: true,
+            ..Default::default()
+        };
+        let entities = parse_spec_with(&yaml, COMPONENTS_POINTER, &config).unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        let code = generator::generate_rust(entities, &GeneratorConfig::default());
+        check_compiles(&code).unwrap();
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubprice:Option<Money>"));
+        assert!(code.contains("pubcurrency:Option<String>"));
+    }
+
+    #[test]
+    fn test_property_order() {
+        let yaml = r#"
+            components:
+              schemas:
+                Ordered:
+                  type: object
+                  properties:
+                    zeta:
+                      type: string
+                    alpha:
+                      type: string
+                    mid:
+                      type: string
+                    nested:
+                      type: object
+                      properties:
+                        second:
+                          type: integer
+                        first:
+                          type: integer
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        let zeta = code.find("pub zeta").unwrap();
+        let alpha = code.find("pub alpha").unwrap();
+        let mid = code.find("pub mid").unwrap();
+        assert!(zeta < alpha && alpha < mid, "{}", code);
+        let second = code.find("pub second").unwrap();
+        let first = code.find("pub first").unwrap();
+        assert!(second < first, "{}", code);
+
+        let entities = parse_spec(yaml).unwrap();
+        let code = generator::generate(entities, TargetLanguage::TypeScript, &Default::default());
+        let zeta = code.find("zeta?:").unwrap();
+        let alpha = code.find("alpha?:").unwrap();
+        let mid = code.find("mid?:").unwrap();
+        assert!(zeta < alpha && alpha < mid, "{}", code);
+    }
+
+    #[test]
+    fn test_map_type() {
+        let yaml = r#"
+            components:
+              schemas:
+                Inventory:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                  additionalProperties:
+                    type: integer
+                Prices:
+                  type: object
+                  additionalProperties:
+                    type: string
+        "#;
+        for (map_type, path) in [
+            (MapType::HashMap, "std::collections::HashMap"),
+            (MapType::BTreeMap, "std::collections::BTreeMap"),
+            (MapType::IndexMap, "indexmap::IndexMap"),
+        ] {
+            let config = GeneratorConfig {
+                map_type,
+                ..Default::default()
+            };
+            let code = generate_rust_with(yaml, &config).unwrap();
+            check_compiles(&code).unwrap();
+            let code = code.replace(' ', "");
+            assert!(code.contains(&format!("pubadditional_properties:{}<String,i32>", path)));
+            assert!(code.contains(&format!("pubstructPrices(pub{}<String,String>);", path)));
+        }
+    }
+
+    #[test]
+    fn test_invalid_schema_is_named() {
+        let yaml = r#"
+            components:
+              schemas:
+                Valid:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                Broken:
+                  type: object
+                  properties: 42
+                AlsoValid:
+                  type: string
+                  enum: [a, b]
+        "#;
+        match parse_spec(yaml) {
+            Err(GenerationError::InvalidSchema(name, _)) => assert_eq!(name, "Broken"),
+            result => panic!("Expected an InvalidSchema error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_no_serde() {
+        let yaml = r#"
+            components:
+              schemas:
+                Circle:
+                  type: object
+                  properties:
+                    kind:
+                      type: string
+                      const: circle
+                    version:
+                      type: integer
+                      const: 2
+                    radius:
+                      type: number
+                      format: double
+                    tags:
+                      type: array
+                      items:
+                        type: string
+                      minItems: 1
+                  required:
+                    - kind
+                    - version
+                    - radius
+                  examples:
+                    - kind: circle
+                      version: 2
+                      radius: 1.5
+                Square:
+                  type: object
+                  properties:
+                    side:
+                      type: integer
+                  required:
+                    - side
+                Shape:
+                  discriminator: shape
+                  oneOf:
+                    - $ref: '#/components/schemas/Circle'
+                    - $ref: '#/components/schemas/Square'
+                Color:
+                  enum: [red, dark-blue]
+                Labels:
+                  type: object
+                  additionalProperties:
+                    type: string
+        "#;
+        let config = GeneratorConfig {
+            no_serde: true,
+            validate: true,
+            bitwise_float_eq: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        assert!(!code.contains("serde"), "{}", code);
+        assert!(!code.contains("monostate"), "{}", code);
+        let code = code.replace(' ', "");
+        assert!(code.contains("#[doc=\"Always`circle`\"]pubkind:String"));
+        assert!(code.contains("pubversion:u64"));
+        assert!(code.contains("kind:\"circle\".to_string(),version:2u64"));
+    }
+
+    #[test]
+    fn test_validation_error_schema() {
+        let yaml = r#"
+            components:
+              schemas:
+                ValidationError:
+                  type: object
+                  properties:
+                    reason:
+                      type: string
+                Tagged:
+                  type: object
+                  properties:
+                    tags:
+                      type: array
+                      items:
+                        type: string
+                      minItems: 1
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        assert_eq!(code.matches("struct ValidationError").count(), 1);
+        let config = GeneratorConfig {
+            validate: true,
+            ..Default::default()
+        };
+        match generate_rust_with(yaml, &config) {
+            Err(GenerationError::Unsupported(name, _)) => assert_eq!(name, "ValidationError"),
+            other => panic!("Expected an Unsupported error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_serde_path() {
+        let yaml = r#"
+            components:
+              schemas:
+                Tagged:
+                  type: object
+                  properties:
+                    tags:
+                      type: array
+                      items:
+                        type: string
+                      minItems: 1
+                  required:
+                    - tags
+        "#;
+        let config = GeneratorConfig {
+            serde_path: "my_serde".to_string(),
+            validate: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap();
+        check_compiles(&format!("extern crate serde as my_serde;\n{}", code))
+            .unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code
+            .contains("my_serde::Deserialize,my_serde::Serialize)]#[serde(crate=\"my_serde\")]"));
+        assert!(code.contains("map_err(my_serde::de::Error::custom)"));
+        assert!(!code.contains("(serde::") && !code.contains(",serde::"));
+    }
+
+    #[test]
+    fn test_string_type() {
+        let yaml = r#"
+            components:
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                    aliases:
+                      type: array
+                      items:
+                        type: string
+                  required:
+                    - name
+                  examples:
+                    - name: Jane
+        "#;
+        for (string_type, path) in [
+            (StringType::BoxStr, "Box<str>"),
+            (StringType::ArcStr, "std::sync::Arc<str>"),
+            (StringType::CompactString, "compact_str::CompactString"),
+        ] {
+            let config = GeneratorConfig {
+                string_type,
+                ..Default::default()
+            };
+            let code = generate_rust_with(yaml, &config).unwrap();
+            check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+            let code = code.replace(' ', "");
+            assert!(code.contains(&format!("pubname:{},", path)));
+            assert!(code.contains(&format!("pubaliases:Option<Vec<{}>>", path)));
+        }
+    }
+
+    #[test]
+    fn test_cow_string_type() {
+        let yaml = r#"
+            components:
+              schemas:
+                Event:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                    tags:
+                      type: array
+                      items:
+                        type: string
+                    count:
+                      type: integer
+                  required:
+                    - name
+                  examples:
+                    - name: created
+        "#;
+        let config = GeneratorConfig {
+            string_type: StringType::Cow,
+            validate: true,
+            encapsulate: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubstructEvent<'a>{"));
+        assert!(code.contains("#[serde(rename=\"name\",borrow)]name:std::borrow::Cow<'a,str>"));
+        assert!(code.contains("impl<'a>Event<'a>{"));
+
+        let yaml = r#"
+            components:
+              schemas:
+                Envelope:
+                  type: object
+                  properties:
+                    event:
+                      $ref: '#/components/schemas/Event'
+                Event:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+        "#;
+        match generate_rust_with(yaml, &config) {
+            Err(GenerationError::UnsupportedBorrow(name, reason)) => {
+                assert_eq!(name, "Event");
+                assert!(reason.contains("Envelope"), "{}", reason);
+            }
+            other => panic!("Expected an UnsupportedBorrow error, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_derive_json_schema() {
+        let yaml = r#"
+            components:
+              schemas:
+                Event:
+                  type: object
+                  properties:
+                    eventType:
+                      type: string
+                      const: created
+                    payload:
+                      type: string
+                  required:
+                    - eventType
+        "#;
+        let config = GeneratorConfig {
+            derive_json_schema: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("serde::Serialize,schemars::JsonSchema)]"));
+        assert!(code.contains("#[schemars(with=\"String\",extend(\"const\"=\"created\"))]"));
+    }
+
+    #[cfg(not(feature = "schemars"))]
+    #[test]
+    fn test_derive_json_schema_without_feature() {
+        let config = GeneratorConfig {
+            derive_json_schema: true,
+            ..Default::default()
+        };
+        match generate_rust_with(include_str!("../resources/asyncapi.yaml"), &config) {
+            Err(GenerationError::Unsupported(option, _)) => {
+                assert_eq!(option, "derive_json_schema")
+            }
+            other => panic!("Expected an Unsupported error, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_derive_json_schema_dependent_required() {
+        let yaml = r#"
+            components:
+              schemas:
+                Checkout:
+                  type: object
+                  properties:
+                    kind:
+                      type: string
+                      const: checkout
+                    credit_card:
+                      type: string
+                    billing_address:
+                      type: string
+                  required:
+                    - kind
+                  dependentRequired:
+                    credit_card:
+                      - billing_address
+        "#;
+        let config = GeneratorConfig {
+            derive_json_schema: true,
+            validate: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains(
+            "#[derive(serde::Deserialize)]#[derive(schemars::JsonSchema)]#[schemars(rename=\"Checkout\")]structCheckoutUnchecked"
+        ));
+    }
+
+    #[cfg(not(feature = "sqlx"))]
+    #[test]
+    fn test_derive_sqlx_without_feature() {
+        let config = GeneratorConfig {
+            derive_sqlx: true,
+            ..Default::default()
+        };
+        match generate_rust_with(include_str!("../resources/asyncapi.yaml"), &config) {
+            Err(GenerationError::Unsupported(option, _)) => assert_eq!(option, "derive_sqlx"),
+            other => panic!("Expected an Unsupported error, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn test_derive_sqlx() {
+        let yaml = r#"
+            components:
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                    status:
+                      $ref: '#/components/schemas/Status'
+                    address:
+                      type: object
+                      properties:
+                        street:
+                          type: string
+                  required:
+                    - name
+                Status:
+                  enum: [active, on-hold]
+        "#;
+        let config = GeneratorConfig {
+            derive_sqlx: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap().replace(' ', "");
+        assert!(code.contains(
+            "PartialEq,sqlx::FromRow,serde::Deserialize,serde::Serialize)]pubstructUser{"
+        ));
+        assert!(code
+            .contains("PartialEq,sqlx::Type,serde::Deserialize,serde::Serialize)]pubenumStatus{"));
+        assert!(code.contains("#[serde(rename=\"on-hold\")]#[sqlx(rename=\"on-hold\")]OnHold"));
+        assert!(code.contains("#[sqlx(json(nullable))]#[serde(rename=\"address\""));
+    }
+
+    #[test]
+    fn test_snake_case_collision() {
+        let yaml = r#"
+            components:
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    userId:
+                      type: string
+                    user_id:
+                      type: integer
+                  required:
+                    - userId
+                    - user_id
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("#[serde(rename=\"userId\")]pubuser_id:String"));
+        assert!(code.contains("#[serde(rename=\"user_id\")]pubuser_id_2:i32"));
+    }
+
+    #[test]
+    fn test_validate_spec() {
+        assert!(validate_spec(include_str!("../resources/asyncapi.yaml")).is_ok());
+        let yaml = r#"
+            components:
+              schemas:
+                Shape:
+                  anyOf:
+                    - type: object
+                      properties:
+                        radius:
+                          type: number
+                Reading:
+                  type: object
+                  properties:
+                    scale:
+                      type: number
+                      enum: [0.5, 1.5]
+        "#;
+        let errors = validate_spec(yaml).unwrap_err();
+        let errors = errors
+            .iter()
+            .map(|error| match error {
+                GenerationError::Unsupported(path, _) => path.as_str(),
+                other => panic!("Expected an Unsupported error, got {:?}", other),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(errors, ["Reading/properties/scale", "Shape"]);
+        // generating reports the first of them instead of panicking
+        match generate_rust(yaml) {
+            Err(GenerationError::Unsupported(path, _)) => {
+                assert_eq!(path, "Reading/properties/scale")
+            }
+            other => panic!("Expected an Unsupported error, got {:?}", other),
+        }
+        let yaml = r#"
+            definitions:
+              Shape:
+                anyOf:
+                  - type: object
+        "#;
+        let errors = validate_spec_at(yaml, "/definitions").unwrap_err();
+        assert!(
+            matches!(&errors[..], [GenerationError::Unsupported(path, _)] if path == "Shape"),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_all_of_primitives() {
+        let yaml = r#"
+            components:
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    id:
+                      allOf:
+                        - type: string
+                        - type: string
+                          format: uuid
+                  required:
+                    - id
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubtypeUserId=uuid::Uuid;"));
+        assert!(code.contains("pubid:UserId"));
+    }
+
+    #[test]
+    fn test_mixed_enum_with_float_and_null() {
+        let yaml = r#"
+            components:
+              schemas:
+                Mixed:
+                  enum: ["a", 1.5, null]
+        "#;
+        match generate_rust(yaml) {
+            Err(GenerationError::Unsupported(path, _)) => assert_eq!(path, "Mixed"),
+            other => panic!("Expected an Unsupported error, got {:?}", other),
+        }
+        assert!(validate_spec(yaml).is_err());
+    }
+
+    #[test]
+    fn test_mixed_enum_colliding_names() {
+        let yaml = r#"
+            components:
+              schemas:
+                Flag:
+                  enum: ["True", true, 1]
+                Spaced:
+                  enum: ["a b", "a-b", 2]
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("True(monostate::MustBe!(\"True\")),True1(monostate::MustBe!(true))"));
+        assert!(code.contains("AB(monostate::MustBe!(\"ab\")),AB1(monostate::MustBe!(\"a-b\"))"));
+    }
+
+    #[test]
+    fn test_draft07_items_tuple() {
+        let yaml = r#"
+            components:
+              schemas:
+                Point:
+                  type: object
+                  properties:
+                    label:
+                      type: array
+                      items:
+                        - type: string
+                        - type: integer
+                          format: int64
+                    path:
+                      type: array
+                      items:
+                        - type: string
+                      additionalItems:
+                        type: boolean
+                  required:
+                    - label
+                    - path
+                  example:
+                    label: [a, 1]
+                    path: [b, true]
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("publabel:(String,i64)"));
+        assert!(code.contains("pubstructAnonymousTuple1(pubString,pubVec<bool>);"));
+    }
+
+    #[test]
+    fn test_float_variant_drops_eq() {
+        let yaml = r#"
+            components:
+              schemas:
+                Reading:
+                  type: object
+                  properties:
+                    value:
+                      type: number
+                      format: double
+                  required: [value]
+                Label:
+                  type: object
+                  properties:
+                    text:
+                      type: string
+                Sample:
+                  oneOf:
+                    - $ref: '#/components/schemas/Reading'
+                    - $ref: '#/components/schemas/Label'
+                Samples:
+                  type: object
+                  properties:
+                    latest:
+                      $ref: '#/components/schemas/Sample'
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("#[derive(Debug,Clone,PartialEq,serde::Deserialize,serde::Serialize)]#[serde(untagged)]pubenumSample"));
+        assert!(code.contains(
+            "#[derive(Debug,Clone,PartialEq,serde::Deserialize,serde::Serialize)]pubstructSamples"
+        ));
+        assert!(code.contains(
+            "#[derive(Debug,Clone,Eq,PartialEq,serde::Deserialize,serde::Serialize)]pubstructLabel"
+        ));
+    }
+
+    #[test]
+    fn test_generate_with_transform() {
+        let yaml = r#"
+            components:
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                Team:
+                  type: object
+                  properties:
+                    lead:
+                      $ref: '#/components/schemas/User'
+                    members:
+                      type: array
+                      items:
+                        $ref: '#/components/schemas/User'
+                Internal:
+                  type: object
+                  properties:
+                    secret:
+                      type: string
+        "#;
+        let code = generate_rust_with_transform(yaml, |entities| {
+            entities.retain(|entity| entity.name != "Internal");
+            let renames = HashMap::from([("User".to_string(), "Person".to_string())]);
+            parser::rename_entities(entities, &renames);
+        })
+        .unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubstructPerson{"));
+        assert!(code.contains("publead:Option<Person>"));
+        assert!(code.contains("pubmembers:Option<Vec<Person>>"));
+        assert!(!code.contains("User"));
+        assert!(!code.contains("Internal"));
+
+        let config = GeneratorConfig {
+            deny_unknown_fields: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with_transform_config(yaml, &config, |_| {}).unwrap();
+        assert!(code.contains("deny_unknown_fields"));
+    }
+
+    #[test]
+    fn test_single_variant_one_of() {
+        let yaml = r#"
+            components:
+              schemas:
+                Event:
+                  oneOf:
+                    - $ref: '#/components/schemas/Click'
+                Click:
+                  type: object
+                  properties:
+                    x:
+                      type: integer
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("#[serde(transparent)]pubstructEvent(pubClick);"));
+        assert!(!code.contains("pubenumEvent"));
+    }
+
+    #[test]
+    fn test_const_schema() {
+        let yaml = r#"
+            components:
+              schemas:
+                Version:
+                  type: string
+                  const: v1
+                Answer:
+                  type: integer
+                  const: 42
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(
+            code.contains("#[serde(transparent)]pubstructVersion(pubmonostate::MustBe!(\"v1\"));")
+        );
+        assert!(
+            code.contains("#[serde(transparent)]pubstructAnswer(pubmonostate::MustBe!(42u64));")
+        );
+        assert!(validate_spec(yaml).is_ok());
+    }
+
+    #[test]
+    fn test_single_value_enum() {
+        let yaml = r#"
+            components:
+              schemas:
+                Order:
+                  type: object
+                  properties:
+                    kind:
+                      type: string
+                      enum: [fixed]
+                  required: [kind]
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains(r#"pubkind:monostate::MustBe!("fixed")"#));
+        assert!(!code.contains("enum"));
+    }
+
+    #[test]
+    fn test_nested_combinator_member() {
+        let yaml = r#"
+            components:
+              schemas:
+                Base:
+                  type: object
+                  properties:
+                    id:
+                      type: string
+                Alert:
+                  allOf:
+                    - $ref: '#/components/schemas/Base'
+                    - oneOf:
+                        - type: object
+                          properties:
+                            email:
+                              type: string
+                        - allOf:
+                            - $ref: '#/components/schemas/Base'
+                            - type: object
+                              properties:
+                                phone:
+                                  type: string
+        "#;
+        let entities = parse_spec(yaml).unwrap();
+        let one_of = entities
+            .iter()
+            .find(|entity| matches!(entity.def, EntityDef::OneOf { .. }))
+            .unwrap();
+        assert!(entities.iter().any(|entity| matches!(
+            &entity.def,
+            EntityDef::AllOf(members) if entity.name == "Alert" && members[1] == one_of.name
+        )));
+        let EntityDef::OneOf { variants, .. } = &one_of.def else {
+            unreachable!()
+        };
+        assert!(entities.iter().any(|entity| matches!(
+            &entity.def,
+            EntityDef::AllOf(members) if entity.name == variants[1] && members[0] == "Base"
+        )));
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+    }
+
+    const BINARY_YAML: &str = r#"
+        components:
+          schemas:
+            Item:
+              type: object
+              properties:
+                kind:
+                  type: string
+                  const: item
+                name:
+                  type: string
+                note:
+                  type: string
+              required: [kind, name]
+              additionalProperties:
+                type: integer
+            Base:
+              type: object
+              properties:
+                id:
+                  type: string
+              required: [id]
+            Order:
+              allOf:
+                - $ref: '#/components/schemas/Base'
+                - type: object
+                  properties:
+                    quantity:
+                      type: integer
+                  required: [quantity]
+    "#;
+
+    #[test]
+    fn test_binary_compatible() {
+        let config = GeneratorConfig {
+            binary_compatible: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(BINARY_YAML, &config).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(!code.contains("MustBe"));
+        assert!(!code.contains("flatten"));
+        assert!(!code.contains("skip_serializing_if"));
+        assert!(code.contains("#[doc=\"Always`item`\"]#[serde(rename=\"kind\")]pubkind:String"));
+        assert!(code.contains("#[serde(default)]pubadditional_properties:"));
+        assert!(code.contains("pubid:String,#[serde(rename=\"quantity\")]pubquantity:i32"));
+    }
+
+    #[test]
+    fn test_binary_compatible_bincode() {
+        let config = GeneratorConfig {
+            binary_compatible: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(BINARY_YAML, &config).unwrap();
+        let main = r#"
+            let mut item = Item::new("book".to_string());
+            item.note = Some("signed".to_string());
+            item.additional_properties.insert("pages".to_string(), 300);
+            let bytes = bincode::serialize(&item).unwrap();
+            let read: Item = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(read, item);
+            assert_eq!(read.kind, "item");
+            let order = Order::new("o-1".to_string(), 2);
+            let bytes = bincode::serialize(&order).unwrap();
+            assert_eq!(bincode::deserialize::<Order>(&bytes).unwrap(), order);
+        "#;
+        check_runs(&code, main).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+    }
+
+    #[test]
+    fn test_enum_descriptions() {
+        let yaml = r#"
+            components:
+              schemas:
+                Status:
+                  enum: [active, on-hold]
+                  x-enumDescriptions:
+                    - The account can be used
+                    - Payments are pending
+                Severity:
+                  enum: [1, 2]
+                  x-enumNames: [Low, High]
+                  x-enum-descriptions: [Can wait, Needs attention now]
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains(r#"#[doc="Theaccountcanbeused"]#[serde(rename="active")]Active"#));
+        assert!(code.contains(r#"#[doc="Paymentsarepending"]#[serde(rename="on-hold")]OnHold"#));
+        assert!(code.contains(r#"#[doc="Needsattentionnow"]High=2"#));
+    }
+
+    #[test]
+    fn test_display_template() {
+        let yaml = r#"
+            components:
+              schemas:
+                Point:
+                  type: object
+                  x-display: '{{{x}, {y}}}'
+                  properties:
+                    x:
+                      type: integer
+                    y:
+                      type: integer
+                  required: [x, y]
+        "#;
+        let code = generate_rust(yaml).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains(r#"write!(f,"{{{},{}}}",self.x,self.y)"#));
+        let yaml = yaml.replace("{y}", "{z}");
+        assert!(matches!(
+            generate_rust(&yaml),
+            Err(GenerationError::UnknownDisplayProperty(name, property)) if name == "Point" && property == "z"
+        ));
+    }
+
+    /// Compares the generated code for `resources/asyncapi.yaml` (run through `rustfmt`) with
+    /// `resources/asyncapi.rs`, `UPDATE_EXPECT=1 cargo test` writes the current output instead
+    #[test]
+    fn test_resources_asyncapi_golden() {
+        use std::{io::Write, process::Command, process::Stdio};
+        let code = generate_rust(include_str!("../resources/asyncapi.yaml")).unwrap();
+        let mut rustfmt = Command::new("rustfmt")
+            .args(["--edition", "2021", "--emit", "stdout"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("rustfmt is needed to compare the generated code");
+        rustfmt
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(code.as_bytes())
+            .unwrap();
+        let output = rustfmt.wait_with_output().unwrap();
+        assert!(output.status.success(), "{}", code);
+        let formatted = String::from_utf8(output.stdout).unwrap();
+        let golden = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/asyncapi.rs");
+        if std::env::var_os("UPDATE_EXPECT").is_some() {
+            std::fs::write(golden, &formatted).unwrap();
+        }
+        assert_eq!(formatted, std::fs::read_to_string(golden).unwrap());
+    }
+
+    #[test]
+    fn test_sequential_generation() {
+        let input = include_str!("../resources/asyncapi.yaml");
+        let sequential = GeneratorConfig {
+            parallel: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_rust_with(input, &sequential).unwrap(),
+            generate_rust_with(input, &GeneratorConfig::default()).unwrap()
+        );
+        let entities = parse_spec(input).unwrap();
+        assert_eq!(
+            generator::generate(entities.clone(), TargetLanguage::TypeScript, &sequential),
+            generator::generate(entities, TargetLanguage::TypeScript, &Default::default())
+        );
+    }
+
+    #[test]
+    fn test_generate_rust_with_report() {
+        let (code, report) =
+            generate_rust_with_report(include_str!("../resources/asyncapi.yaml")).unwrap();
+        let source = |pointer: &str| {
+            report
+                .iter()
+                .find(|(source, _)| source == pointer)
+                .map(|(_, name)| name.as_str())
+        };
+        assert_eq!(
+            source("#/components/schemas/RequestBase"),
+            Some("RequestBase")
+        );
+        // the inline variant of the `oneOf` doesn't have a title
+        let anonymous = source("#/components/schemas/GetUser/oneOf/1").unwrap();
+        assert!(anonymous.starts_with("AnonymousEntity"), "{}", anonymous);
+        assert!(code.contains(&format!("pub struct {}", anonymous)));
+        assert_eq!(
+            source("#/components/schemas/GetUser/oneOf/1/properties/data"),
+            Some("GetUserData")
+        );
+        assert_eq!(
+            source("#/components/schemas/DeleteUser/allOf/1/properties/data"),
+            Some("DeleteUserData")
+        );
+        // every generated type is accounted for
+        assert_eq!(
+            report.len(),
+            code.matches("pub struct ").count() + code.matches("pub enum ").count()
+        );
+
+        let config = GeneratorConfig {
+            deny_unknown_fields: true,
+            ..Default::default()
+        };
+        let (code, _) =
+            generate_rust_with_report_config(include_str!("../resources/asyncapi.yaml"), &config)
+                .unwrap();
+        assert!(code.contains("deny_unknown_fields"));
+    }
+
+    #[test]
+    fn test_empty_properties() {
+        let yaml = r#"
+            components:
+              schemas:
+                Empty:
+                  type: object
+                  properties: {}
+                Strict:
+                  type: object
+                  properties: {}
+                  additionalProperties: false
+        "#;
+        let config = GeneratorConfig {
+            deny_unknown_fields: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap();
+        check_compiles(&code).unwrap_or_else(|stderr| panic!("{}\n{}", stderr, code));
+        let code = code.replace(' ', "");
+        assert!(code.contains("pubstructEmpty{}"), "{}", code);
+        assert!(code.contains("pubstructStrict{}"), "{}", code);
+    }
+
+    #[test]
+    fn test_inline_all_of() {
+        let yaml = include_str!("../resources/asyncapi.yaml");
+        let flattened = generate_rust(yaml).unwrap().replace(' ', "");
+        assert!(flattened.contains("#[serde(flatten)]pubrequest_base:RequestBase"));
+
+        let config = GeneratorConfig {
+            inline_all_of: true,
+            deny_unknown_fields: true,
+            ..Default::default()
+        };
+        let code = generate_rust_with(yaml, &config).unwrap();
+        check_compiles(&code).unwrap();
+        let code = code.replace(' ', "");
+        let delete_user = &code[code.find("pubstructDeleteUser{").unwrap()..];
+        let delete_user = &delete_user[..delete_user.find('}').unwrap()];
+        assert!(!delete_user.contains("flatten"));
+        for field in [
+            "pubid:String",
+            "pubkind:",
+            "pubchild:",
+            "pubevent:",
+            "pubdata:",
+        ] {
+            assert!(delete_user.contains(field), "{}", delete_user);
+        }
+        assert!(code.contains("#[serde(deny_unknown_fields)]pubstructDeleteUser"));
+        assert!(code.contains(
+            "implFrom<&DeleteUser>forRequestBase{fnfrom(value:&DeleteUser)->Self{Self{id:value.id.clone(),kind:value.kind.clone()}}}"
+        ));
+    }
+
+    #[test]
+    fn test_rust_type_extension() {
+        let yaml = r#"
+            components:
+              schemas:
+                Holder:
+                  type: object
+                  properties:
+                    special:
+                      type: string
+                      x-rust-type: my_crate::Special
+                      x-go-type: Special
+                      description: ignored
+                    plain:
+                      type: string
+                  required:
+                    - special
+                    - plain
+        "#;
+        let entities = parse_spec(yaml).unwrap();
+        let EntityDef::Struct(StructDef { properties, .. }) = &entities[0].def else {
+            panic!("Expected a Struct definition");
+        };
+        let extensions = &properties["special"].extensions;
+        assert_eq!(extensions.0.len(), 2);
+        assert_eq!(extensions.get("x-go-type").unwrap(), "Special");
+        let code = generator::generate_rust(entities, &GeneratorConfig::default()).replace(' ', "");
+        assert!(code.contains("pubspecial:my_crate::Special"));
+        assert!(code.contains("pubplain:String"));
+    }
+
+    #[test]
+    fn test_integer_formats() {
+        let yaml = r#"
+            components:
+              schemas:
+                Sizes:
+                  type: object
+                  properties:
+                    tiny:
+                      type: integer
+                      format: int8
+                    huge:
+                      type: integer
+                      format: int256
+                  required:
+                    - tiny
+                    - huge
+        "#;
+        let code = generate_rust(yaml).unwrap().replace(' ', "");
+        assert!(code.contains("pubtiny:i8"));
+        assert!(code.contains("pubhuge:i32"));
+    }
+
+    #[test]
+    fn test_deterministic_output() {
+        let yaml = r#"
+            components:
+              schemas:
+                Zebra:
+                  type: object
+                  properties:
+                    stripes:
+                      type: integer
+                Apple:
+                  type: object
+                  properties:
+                    color:
+                      title: Color
+                      enum: [red, green]
+                Mango:
+                  enum: [ripe]
+                Banana:
+                  type: object
+                  properties:
+                    length:
+                      type: number
+        "#;
+        let names = parse_spec(yaml)
+            .unwrap()
+            .into_iter()
+            .map(|entity| entity.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Apple", "Banana", "Color", "Mango", "Zebra"]);
+        let code = generate_rust(yaml).unwrap();
+        for _ in 0..10 {
+            assert_eq!(generate_rust(yaml).unwrap(), code);
+        }
+    }
 }
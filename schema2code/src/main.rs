@@ -0,0 +1,70 @@
+use std::{path::PathBuf, process::Command};
+
+use anyhow::Context;
+use clap::{Parser, ValueEnum};
+use schema2code::{GeneratorConfig, TargetLanguage, COMPONENTS_POINTER};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Target {
+    Rust,
+    Protobuf,
+    #[value(name = "typescript")]
+    TypeScript,
+}
+
+impl From<Target> for TargetLanguage {
+    fn from(target: Target) -> Self {
+        match target {
+            Target::Rust => TargetLanguage::Rust,
+            Target::Protobuf => TargetLanguage::Protobuf,
+            Target::TypeScript => TargetLanguage::TypeScript,
+        }
+    }
+}
+
+/// Generate code from the schemas of an asyncapi/JSON schema document
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    /// The specification to read, yaml or json
+    #[arg(long)]
+    input: PathBuf,
+    /// Where to write the generated code, prints to stdout if omitted
+    #[arg(long)]
+    output: Option<PathBuf>,
+    #[arg(long, value_enum, default_value = "rust")]
+    target: Target,
+    /// JSON pointer to the mapping of schemas inside the document
+    #[arg(long, default_value = COMPONENTS_POINTER)]
+    components_pointer: String,
+    /// Run `rustfmt` over the generated file, only applies to `--target rust` with `--output`
+    #[arg(long)]
+    format: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let input = std::fs::read_to_string(&args.input)
+        .with_context(|| format!("Failed to read {}", args.input.display()))?;
+    let entities = schema2code::parse_spec_at(&input, &args.components_pointer)?;
+    let code = schema2code::generator::generate_checked(
+        entities,
+        args.target.into(),
+        &GeneratorConfig::default(),
+    )?;
+    match args.output {
+        Some(output) => {
+            std::fs::write(&output, code)
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+            if args.format && matches!(args.target, Target::Rust) {
+                let status = Command::new("rustfmt")
+                    .arg(&output)
+                    .status()
+                    .context("Failed to run rustfmt")?;
+                anyhow::ensure!(status.success(), "rustfmt failed on {}", output.display());
+            }
+        }
+        None => println!("{}", code),
+    }
+    Ok(())
+}
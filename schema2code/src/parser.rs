@@ -1,9 +1,18 @@
-use std::{collections::HashMap, sync::atomic::AtomicU32};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::AtomicU32,
+};
 
+use indexmap::IndexMap;
 use lazy_static::lazy_static;
-use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
-use crate::deserializer::{AdditionalProperties, Format, PrimitiveType, Schema, SchemaDef};
+use crate::{
+    deserializer::{
+        AdditionalProperties, Extensions, Format, IntegerValue, MessageDef, Not, PrimitiveType,
+        Schema, SchemaDef, TupleItems, TypeName,
+    },
+    map_items, GenerationError,
+};
 
 /// A type for a field in a struct
 #[derive(Debug, Clone)]
@@ -25,6 +34,9 @@ pub enum FieldType {
     /// For example, if we had a `Named("MyType")` and a `Simple(Primitive::Long)`,
     /// the resulting type would be this in rust: `(Box<MyType>, i64)` or in python: `tuple[MyType, int]`
     Tuple(Vec<FieldType>),
+    /// A type that needs indirection to be representable, e.g. a tuple member referencing the
+    /// entity that contains it. In Rust: `Box<T>`, languages with reference semantics ignore it.
+    Boxed(Box<FieldType>),
     /// A simple type, representing a primitive type of the language that is being used for
     /// generation
     Simple(Primitive),
@@ -36,16 +48,43 @@ pub enum FieldType {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Primitive {
+    I8,
+    I16,
     Int,
+    Long,
+    I128,
+    U8,
+    U16,
     U32,
     U64,
-    Long,
+    U128,
     Float,
     Double,
     String,
     Bool,
     Uuid,
     Bytes,
+    /// A high-precision decimal number, only produced with the `decimal` feature
+    Decimal,
+}
+
+/// A validation rule of a schema that can't be expressed through the type of a field
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constraint {
+    /// `minItems` of an array
+    MinItems(u64),
+    /// `maxItems` of an array
+    MaxItems(u64),
+    /// `uniqueItems: true` of an array
+    UniqueItems,
+    /// `propertyNames: {pattern: ...}` of an object, a regex every key has to match
+    PropertyNamePattern(String),
+    /// `not: {const: ...}` or `not: {enum: [...]}`, the values a primitive must not have
+    Not(Vec<serde_json::Value>),
+    /// `minProperties` of a map-like object
+    MinProperties(u64),
+    /// `maxProperties` of a map-like object
+    MaxProperties(u64),
 }
 
 /// A type for a field in a struct/class
@@ -53,19 +92,40 @@ pub enum Primitive {
 pub struct Field {
     pub optional: bool,
     pub field_type: FieldType,
+    pub constraints: Vec<Constraint>,
+    /// `writeOnly: true`, e.g. a password that's only ever sent to the server
+    pub write_only: bool,
+    /// The vendor extensions of the property schema, `x-rust-type` overrides the generated type
+    pub extensions: Extensions,
 }
 
-/// The definition for a Struct/Class like type
+/// The definition for a Struct/Class like type, `properties` keep the order they are declared in
 #[derive(Debug, Clone)]
 pub struct StructDef {
-    pub properties: HashMap<String, Field>,
+    pub properties: IndexMap<String, Field>,
     pub additional_properties: Option<FieldType>,
+    /// The first `examples` (or `example`) value of the schema
+    pub example: Option<serde_json::Value>,
+    /// `dependentRequired`, the properties that are required whenever the key is present
+    pub dependent_required: IndexMap<String, Vec<String>>,
+    /// The `x-display` template of the schema, e.g. `{firstName} {lastName}`, properties in
+    /// braces are replaced with their values. In Rust: an `impl Display`
+    pub display: Option<String>,
 }
 
 /// Definition for an Enumeration
 #[derive(Debug, Clone)]
 pub struct EnumDef {
     pub values: Vec<String>,
+    /// The variant names from `x-enumNames` (or `x-enum-varnames`) paired with `values` by
+    /// position, empty if the variant names are derived from the values
+    pub names: Vec<String>,
+    /// The descriptions from `x-enumDescriptions` paired with `values` by position, empty if
+    /// there are none. In Rust: the doc comments of the variants
+    pub descriptions: Vec<String>,
+    /// The integer type of the values if they aren't strings, e.g. an `integer` enum with
+    /// `x-enumNames`. In Rust: a `#[repr(...)]` enum with the values as discriminants
+    pub repr: Option<Primitive>,
 }
 
 /// A definition for the types that need to be generated
@@ -79,11 +139,14 @@ pub enum EntityDef {
     /// e.g. in Rust the `discriminant` would represent the value inside of
     /// `#[serde(tag="<discriminant>")]`, if not provided `#[serde(untagged)]` is used
     /// Specific values for discriminants that need to be placed in `#[serde(rename="<value>")]`
-    /// will be scanned in `Const` fields in the Entity types of the variants (this feature is WIP
-    /// and not prioritized yet)
+    /// come from the `mapping` of the discriminator, or from a `Const` discriminant property of
+    /// the variant's struct when the mapping doesn't list the variant
     OneOf {
         discriminant: Option<String>,
         variants: Vec<String>,
+        /// The value of the discriminant for variants where it isn't the variant name itself,
+        /// taken from the `mapping` of the discriminator or the variant's `Const` discriminant
+        tags: HashMap<String, String>,
     },
     /// AllOf is the inheritance operator, all structs that are combined are referenced by name and
     /// expected to exist.
@@ -91,6 +154,21 @@ pub enum EntityDef {
     /// A definition for an Enumeration in a classical sense, a collection of possible values of a
     /// single type
     Enum(EnumDef),
+    /// A positional type, used for tuples that have too many members to be represented by the
+    /// target language's native tuple, e.g. in Rust: `struct Name(A, B, ...)`, or that have a
+    /// variadic tail: the type of the items following the members (`items` next to `prefixItems`).
+    /// In Rust the tail is a trailing `Vec<Tail>` member, (de)serialized as part of the same array
+    Tuple(Vec<FieldType>, Option<FieldType>),
+    /// An Enumeration whose values don't share a single type, e.g. `["active", 1, true]`.
+    /// Every value is a `FieldType::Const`, or a `FieldType::Simple` for a `type` array like
+    /// `[integer, string]`, in Rust this becomes an untagged enum with a variant per value
+    MixedEnum(Vec<FieldType>),
+    /// An object without `properties` whose values are described by `additionalProperties`, the
+    /// value type is `None` for untyped values. In Rust: a transparent newtype around a `HashMap`
+    Map(Option<FieldType>, Vec<Constraint>),
+    /// Another name for a type, e.g. a message whose payload is a `$ref` to a schema or an array
+    /// of them. In Rust: `type Name = Target;`
+    Alias(FieldType),
 }
 
 /// An entity is any kind of type that needs to be generated in the result code
@@ -101,9 +179,37 @@ pub struct Entity {
     pub def: EntityDef,
 }
 
+/// Document-wide information the parser needs while parsing a single schema
+#[derive(Debug, Clone)]
+pub struct ParseContext {
+    /// `$ref` targets that don't have a named entity of their own (e.g. a pointer to a `string`
+    /// property of another schema), keyed by the full pointer. They get parsed in place of the ref.
+    pub resolved_refs: HashMap<String, Schema>,
+    /// Whether the schemas of a collection are parsed on the rayon thread pool
+    pub parallel: bool,
+    /// The names of the top-level schemas, an inline entity named after its parent and property
+    /// (e.g. `UserAddress`) that would clash with one gets an anonymous name instead
+    pub schema_names: HashSet<String>,
+}
+
+impl Default for ParseContext {
+    fn default() -> Self {
+        Self {
+            resolved_refs: HashMap::new(),
+            parallel: true,
+            schema_names: HashSet::new(),
+        }
+    }
+}
+
+/// Tuples with more members than this get generated as a positional `EntityDef::Tuple` instead,
+/// the standard library only implements `Debug`, `Clone`, `Eq`, ... for tuples up to 12 members.
+const MAX_TUPLE_LEN: usize = 12;
+
 lazy_static! {
     static ref ANONYMOUS_STRUCT_COUNT: AtomicU32 = AtomicU32::new(1);
     static ref ANONYMOUS_ENUM_COUNT: AtomicU32 = AtomicU32::new(1);
+    static ref ANONYMOUS_TUPLE_COUNT: AtomicU32 = AtomicU32::new(1);
 }
 
 fn generate_struct_name() -> String {
@@ -113,18 +219,143 @@ fn generate_struct_name() -> String {
     )
 }
 
+/// Turns a property name or channel address like `home_address`, `user/signed-up` or
+/// `{userId}.events` into `HomeAddress`, `UserSignedUp` and `UserIdEvents`
+pub(crate) fn pascal_case(s: &str) -> String {
+    s.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let (first, rest) = segment.split_at(1);
+            format!("{}{}", first.to_uppercase(), rest)
+        })
+        .collect()
+}
+
+fn generate_enum_name() -> String {
+    format!(
+        "AnonymousEnum{}",
+        ANONYMOUS_ENUM_COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+    )
+}
+
+/// The strings an `enum` lists per value in the first of the extensions `keys` it has, they're
+/// only taken if there is one for every value
+fn per_value_strings(extensions: &Extensions, keys: &[&str], value_count: usize) -> Vec<String> {
+    keys.iter()
+        .find_map(|key| extensions.get(key)?.as_sequence())
+        .and_then(|strings| {
+            strings
+                .iter()
+                .map(|string| string.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        })
+        .filter(|strings| strings.len() == value_count)
+        .unwrap_or_default()
+}
+
+/// The variant names an `enum` declares with `x-enumNames` (or `x-enum-varnames`)
+fn enum_names(extensions: &Extensions, value_count: usize) -> Vec<String> {
+    per_value_strings(extensions, &["x-enumNames", "x-enum-varnames"], value_count)
+}
+
+/// The descriptions an `enum` gives its values with `x-enumDescriptions` (or
+/// `x-enum-descriptions`)
+fn enum_descriptions(extensions: &Extensions, value_count: usize) -> Vec<String> {
+    per_value_strings(
+        extensions,
+        &["x-enumDescriptions", "x-enum-descriptions"],
+        value_count,
+    )
+}
+
+/// An `integer` enum, named by `x-enumNames` it becomes a `#[repr]` enum, otherwise an untagged
+/// `MixedEnum` of the integer constants
+fn integer_enum(values: Vec<IntegerValue>, extensions: &Extensions) -> EntityDef {
+    let names = enum_names(extensions, values.len());
+    if !names.is_empty() && values.iter().all(|value| i64::try_from(value.0).is_ok()) {
+        EntityDef::Enum(EnumDef {
+            descriptions: enum_descriptions(extensions, values.len()),
+            values: values.iter().map(ToString::to_string).collect(),
+            names,
+            repr: Some(Primitive::Long),
+        })
+    } else {
+        EntityDef::MixedEnum(
+            values
+                .into_iter()
+                .map(|value| FieldType::Const(Primitive::Long, value.to_string()))
+                .collect(),
+        )
+    }
+}
+
+/// The error for a schema at `path` the parser has no representation for
+fn unsupported(path: &str, reason: &str) -> GenerationError {
+    GenerationError::Unsupported(path.to_string(), reason.to_string())
+}
+
+/// Turns a literal of an `enum` without a `type` or of an object `const` into a `FieldType::Const`
+fn parse_literal(value: serde_json::Value, path: &str) -> Result<FieldType, GenerationError> {
+    match value {
+        serde_json::Value::String(value) => Ok(FieldType::Const(Primitive::String, value)),
+        serde_json::Value::Bool(value) => Ok(FieldType::Const(Primitive::Bool, value.to_string())),
+        serde_json::Value::Number(value) if value.is_i64() || value.is_u64() => {
+            Ok(FieldType::Const(Primitive::Long, value.to_string()))
+        }
+        _ => Err(unsupported(
+            path,
+            "literals can only be strings, integers or booleans",
+        )),
+    }
+}
+
 /// Parses a 2nd level and below Schema element into a FieldType and a list of Entities that might be correlated to the
 /// field (e.g. anonymous structs that are nested below a field, which will need to be generated or
 /// the object type of the field itself that is inlined)
-/// It recursively uses `parse_entity` to generate entities for non-primitive types
-fn parse_schema(schema: Schema) -> (FieldType, Vec<Entity>) {
-    match schema {
-        Schema::Ref(schema_ref) => {
-            // TODO: handle ref '#' to self for self-referential types
-            let name = schema_ref.get_schema_name().to_string();
-            (FieldType::Named(name), vec![])
-        }
+/// It recursively uses `parse_entity` to generate entities for non-primitive types.
+/// Inline entities without a `title` are named `name_hint` if there is one (e.g. `UserAddress` for
+/// the `address` property of `User`), otherwise they get an anonymous name.
+/// `root` is the top-level entity the schema belongs to, the target of a `$ref: '#'`, and `path`
+/// where the schema is, e.g. `User/properties/address`, for the error if it can't be parsed.
+fn parse_schema(
+    schema: Schema,
+    ctx: &ParseContext,
+    root: &str,
+    name_hint: Option<&str>,
+    path: &str,
+) -> Result<(FieldType, Vec<Entity>), GenerationError> {
+    let inline_name = |title: &Option<String>| {
+        title
+            .clone()
+            .or_else(|| {
+                name_hint
+                    .filter(|name| !ctx.schema_names.contains(*name))
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(generate_struct_name)
+    };
+    Ok(match schema {
+        Schema::Ref(schema_ref) => match ctx.resolved_refs.get(&schema_ref.schema_path) {
+            Some(resolved) => parse_schema(resolved.clone(), ctx, root, name_hint, path)?,
+            // `#` is the root of the document, i.e. the top-level entity this schema belongs to
+            None if schema_ref.schema_path == "#" => (
+                FieldType::Boxed(Box::new(FieldType::Named(root.to_string()))),
+                vec![],
+            ),
+            None => (FieldType::Named(schema_ref.get_schema_name()), vec![]),
+        },
         Schema::Def(schema_def) => match schema_def {
+            SchemaDef::Object {
+                const_value: Some(_),
+                ref title,
+                ..
+            } => {
+                let inner_schema_name = inline_name(title);
+                (
+                    FieldType::Named(inner_schema_name.clone()),
+                    parse_entity(schema_def, inner_schema_name, ctx, root, path)?,
+                )
+            }
             // `properties = None` indicates a `HashMap` type
             SchemaDef::Object {
                 properties: None,
@@ -133,7 +364,9 @@ fn parse_schema(schema: Schema) -> (FieldType, Vec<Entity>) {
             } => match additional_properties {
                 AdditionalProperties::Boolean(_) => (FieldType::Object(None), vec![]),
                 AdditionalProperties::Schema(schema) => {
-                    let (field_type, entities) = parse_schema(*schema);
+                    let path = format!("{}/additionalProperties", path);
+                    let (field_type, entities) =
+                        parse_schema(*schema, ctx, root, name_hint, &path)?;
                     (FieldType::Object(Some(Box::new(field_type))), entities)
                 }
             },
@@ -141,54 +374,101 @@ fn parse_schema(schema: Schema) -> (FieldType, Vec<Entity>) {
             | SchemaDef::AllOf { ref title, .. }
             | SchemaDef::OneOf { ref title, .. }
             | SchemaDef::AnyOf { ref title, .. } => {
-                let inner_schema_name = title.clone().unwrap_or_else(generate_struct_name);
+                let inner_schema_name = inline_name(title);
                 (
                     FieldType::Named(inner_schema_name.clone()),
-                    parse_entity(schema_def, inner_schema_name),
+                    parse_entity(schema_def, inner_schema_name, ctx, root, path)?,
+                )
+            }
+            SchemaDef::Enum { ref title, .. } => {
+                let enum_name = title.clone().unwrap_or_else(generate_enum_name);
+                (
+                    FieldType::Named(enum_name.clone()),
+                    parse_entity(schema_def, enum_name, ctx, root, path)?,
                 )
             }
-            SchemaDef::String { type_def, .. } => match type_def {
+            SchemaDef::String {
+                type_def,
+                content_encoding,
+                extensions,
+                ..
+            } => match type_def {
                 PrimitiveType::Const { const_value } => {
                     (FieldType::Const(Primitive::String, const_value), vec![])
                 }
+                // a single value can't be anything else, that's a `const`
+                PrimitiveType::Enum { mut enum_values } if enum_values.len() == 1 => (
+                    FieldType::Const(Primitive::String, enum_values.remove(0)),
+                    vec![],
+                ),
                 PrimitiveType::Enum { enum_values } => {
                     let def = EntityDef::Enum(EnumDef {
+                        names: enum_names(&extensions, enum_values.len()),
+                        descriptions: enum_descriptions(&extensions, enum_values.len()),
                         values: enum_values,
+                        repr: None,
                     });
-                    let name = format!(
-                        "AnonymousEnum{}",
-                        ANONYMOUS_ENUM_COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
-                    );
+                    let name = generate_enum_name();
                     let field_type = FieldType::Named(name.clone());
                     let enum_entity = Entity { name, def };
                     (field_type, vec![enum_entity])
                 }
+                // `contentEncoding: base64` is the JSON schema way of saying `format: byte`
+                PrimitiveType::Basic { .. } if content_encoding.as_deref() == Some("base64") => {
+                    (FieldType::Simple(Primitive::Bytes), vec![])
+                }
                 PrimitiveType::Basic { format, .. } => match format {
                     Some(Format::Uuid) => (FieldType::Simple(Primitive::Uuid), vec![]),
                     Some(Format::Byte) => (FieldType::Simple(Primitive::Bytes), vec![]),
+                    #[cfg(feature = "decimal")]
+                    Some(Format::Decimal) => (FieldType::Simple(Primitive::Decimal), vec![]),
                     _ => (FieldType::Simple(Primitive::String), vec![]),
                 },
             },
-            SchemaDef::Integer { type_def, .. } => match type_def {
-                PrimitiveType::Const { const_value: _ } => todo!(),
-                PrimitiveType::Enum { enum_values: _ } => todo!(),
+            SchemaDef::Integer {
+                type_def,
+                extensions,
+                ..
+            } => match type_def {
+                PrimitiveType::Const { const_value } => (
+                    FieldType::Const(Primitive::Long, const_value.to_string()),
+                    vec![],
+                ),
+                PrimitiveType::Enum { enum_values } if enum_values.len() == 1 => (
+                    FieldType::Const(Primitive::Long, enum_values[0].to_string()),
+                    vec![],
+                ),
+                PrimitiveType::Enum { enum_values } => {
+                    let def = integer_enum(enum_values, &extensions);
+                    let name = generate_enum_name();
+                    let field_type = FieldType::Named(name.clone());
+                    (field_type, vec![Entity { name, def }])
+                }
                 PrimitiveType::Basic { format, minimum } => match format {
+                    Some(Format::Int8) => (FieldType::Simple(Primitive::I8), vec![]),
+                    Some(Format::Int16) => (FieldType::Simple(Primitive::I16), vec![]),
+                    Some(Format::Int128) => (FieldType::Simple(Primitive::I128), vec![]),
+                    Some(Format::Uint8) => (FieldType::Simple(Primitive::U8), vec![]),
+                    Some(Format::Uint16) => (FieldType::Simple(Primitive::U16), vec![]),
+                    Some(Format::Uint32) => (FieldType::Simple(Primitive::U32), vec![]),
+                    Some(Format::Uint64) => (FieldType::Simple(Primitive::U64), vec![]),
+                    Some(Format::Uint128) => (FieldType::Simple(Primitive::U128), vec![]),
                     Some(Format::Int64) => {
-                        if minimum.is_some_and(|min| min >= 0) {
+                        if minimum.is_some_and(|min| min.0 >= 0) {
                             (FieldType::Simple(Primitive::U64), vec![])
                         } else {
                             (FieldType::Simple(Primitive::Long), vec![])
                         }
                     }
                     Some(Format::Int32) => {
-                        if minimum.is_some_and(|min| min >= 0) {
+                        if minimum.is_some_and(|min| min.0 >= 0) {
                             (FieldType::Simple(Primitive::U32), vec![])
                         } else {
                             (FieldType::Simple(Primitive::Int), vec![])
                         }
                     }
                     _ => {
-                        if minimum.is_some_and(|min| min >= 0) {
+                        if minimum.is_some_and(|min| min.0 >= 0) {
                             (FieldType::Simple(Primitive::U64), vec![])
                         } else {
                             (FieldType::Simple(Primitive::Int), vec![])
@@ -198,45 +478,308 @@ fn parse_schema(schema: Schema) -> (FieldType, Vec<Entity>) {
             },
             SchemaDef::Boolean { .. } => (FieldType::Simple(Primitive::Bool), vec![]),
             SchemaDef::Number { type_def, .. } => match type_def {
-                PrimitiveType::Const { const_value: _ } => todo!(),
-                PrimitiveType::Enum { enum_values: _ } => todo!(),
+                PrimitiveType::Const { .. } | PrimitiveType::Enum { .. } => {
+                    return Err(unsupported(path, "a `number` can't be a `const` or `enum`"))
+                }
                 PrimitiveType::Basic { format, .. } => match format {
                     Some(Format::Float) => (FieldType::Simple(Primitive::Float), vec![]),
                     Some(Format::Double) => (FieldType::Simple(Primitive::Double), vec![]),
-                    _ => (FieldType::Simple(Primitive::Float), vec![]),
+                    #[cfg(feature = "decimal")]
+                    Some(Format::Decimal) => (FieldType::Simple(Primitive::Decimal), vec![]),
+                    // without an explicit `format: float` the bounds and precision may not fit `f32`
+                    _ => (FieldType::Simple(Primitive::Double), vec![]),
                 },
             },
             SchemaDef::Array { items, .. } => match items {
                 Some(schema) => {
-                    let (field_type, entities) = parse_schema(*schema);
+                    let path = format!("{}/items", path);
+                    let (field_type, entities) =
+                        parse_schema(*schema, ctx, root, name_hint, &path)?;
                     (FieldType::Array(Some(Box::new(field_type))), entities)
                 }
                 None => (FieldType::Array(None), vec![]),
             },
-            SchemaDef::Tuple { prefix_items, .. } => {
+            SchemaDef::MultiType {
+                title,
+                types,
+                format,
+            } => {
+                let mut field_types = parse_multi_type(types, format, ctx, root, path)?;
+                match field_types.len() {
+                    // only `null`
+                    0 => (FieldType::Object(None), vec![]),
+                    1 => (field_types.remove(0), vec![]),
+                    _ => {
+                        let name = title
+                            .or_else(|| name_hint.map(str::to_string))
+                            .unwrap_or_else(generate_enum_name);
+                        let entity = Entity {
+                            name: name.clone(),
+                            def: EntityDef::MixedEnum(field_types),
+                        };
+                        (FieldType::Named(name), vec![entity])
+                    }
+                }
+            }
+            SchemaDef::Tuple {
+                prefix_items,
+                items,
+                ..
+            } => {
                 let mut entities = vec![];
-                let field_types = prefix_items
-                    .into_iter()
-                    .map(|tuple_item| {
-                        let (field_type, mut parsed_entities) = parse_schema(tuple_item);
+                let mut field_types = vec![];
+                for (index, tuple_item) in prefix_items.into_iter().enumerate() {
+                    let path = format!("{}/prefixItems/{}", path, index);
+                    let (field_type, mut parsed_entities) =
+                        parse_schema(tuple_item, ctx, root, None, &path)?;
+                    entities.append(&mut parsed_entities);
+                    field_types.push(field_type);
+                }
+                let tail = match items {
+                    TupleItems::Closed(_) => None,
+                    TupleItems::Tail(schema) => {
+                        let path = format!("{}/items", path);
+                        let (field_type, mut parsed_entities) =
+                            parse_schema(*schema, ctx, root, None, &path)?;
                         entities.append(&mut parsed_entities);
-                        field_type
-                    })
-                    .collect();
-                (FieldType::Tuple(field_types), entities)
+                        Some(field_type)
+                    }
+                };
+                // a native tuple has a fixed length
+                if field_types.len() > MAX_TUPLE_LEN || tail.is_some() {
+                    let name = format!(
+                        "AnonymousTuple{}",
+                        ANONYMOUS_TUPLE_COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+                    );
+                    entities.push(Entity {
+                        name: name.clone(),
+                        def: EntityDef::Tuple(field_types, tail),
+                    });
+                    (FieldType::Named(name), entities)
+                } else {
+                    (FieldType::Tuple(field_types), entities)
+                }
             }
         },
+    })
+}
+
+/// Parses the non-null types of a `type` array into their `FieldType`, integers are placed before
+/// numbers so an untagged enum of them picks the narrower type
+fn parse_multi_type(
+    mut types: Vec<TypeName>,
+    format: Option<Format>,
+    ctx: &ParseContext,
+    root: &str,
+    path: &str,
+) -> Result<Vec<FieldType>, GenerationError> {
+    types.sort();
+    types.dedup();
+    types
+        .into_iter()
+        .filter_map(|type_name| {
+            let schema_def = match type_name {
+                TypeName::Boolean => SchemaDef::Boolean {
+                    schema_type: Default::default(),
+                    extensions: Default::default(),
+                },
+                TypeName::Integer => SchemaDef::Integer {
+                    schema_type: Default::default(),
+                    type_def: PrimitiveType::Basic {
+                        format: format.clone(),
+                        minimum: None,
+                    },
+                    write_only: false,
+                    not: None,
+                    extensions: Default::default(),
+                },
+                TypeName::Number => SchemaDef::Number {
+                    schema_type: Default::default(),
+                    type_def: PrimitiveType::Basic {
+                        format: format.clone(),
+                        minimum: None,
+                    },
+                    write_only: false,
+                    not: None,
+                    extensions: Default::default(),
+                },
+                TypeName::String => SchemaDef::String {
+                    schema_type: Default::default(),
+                    type_def: PrimitiveType::Basic {
+                        format: format.clone(),
+                        minimum: None,
+                    },
+                    write_only: false,
+                    content_encoding: None,
+                    not: None,
+                    extensions: Default::default(),
+                },
+                TypeName::Null => return None,
+            };
+            Some(
+                parse_schema(Schema::Def(schema_def), ctx, root, None, path)
+                    .map(|(field_type, _)| field_type),
+            )
+        })
+        .collect()
+}
+
+/// Whether a primitive schema is marked `writeOnly`
+fn is_write_only(schema: &Schema) -> bool {
+    matches!(
+        schema,
+        Schema::Def(
+            SchemaDef::String {
+                write_only: true,
+                ..
+            } | SchemaDef::Integer {
+                write_only: true,
+                ..
+            } | SchemaDef::Number {
+                write_only: true,
+                ..
+            }
+        )
+    )
+}
+
+/// Whether `null` is one of the types of a schema, e.g. `type: [string, "null"]`, or the schema
+/// is marked with the `x-nullable: true` extension some older tools emit
+fn is_nullable(schema: &Schema) -> bool {
+    let x_nullable = schema
+        .extensions()
+        .and_then(|extensions| extensions.get("x-nullable"))
+        .and_then(serde_yaml::Value::as_bool);
+    x_nullable == Some(true)
+        || matches!(schema, Schema::Def(SchemaDef::MultiType { types, .. }) if types.contains(&TypeName::Null))
+}
+
+/// Wraps members of tuples that reference the entity `name` in a `FieldType::Boxed`, a tuple
+/// is stored inline so a member of the enclosing type would make it infinitely sized.
+fn box_self_references(field_type: FieldType, name: &str) -> FieldType {
+    match field_type {
+        FieldType::Tuple(members) => FieldType::Tuple(box_tuple_members(members, name)),
+        field_type => field_type,
+    }
+}
+
+fn box_tuple_members(members: Vec<FieldType>, name: &str) -> Vec<FieldType> {
+    members
+        .into_iter()
+        .map(|member| match member {
+            FieldType::Named(ref member_name) if member_name == name => {
+                FieldType::Boxed(Box::new(member))
+            }
+            member => box_self_references(member, name),
+        })
+        .collect()
+}
+
+/// Collects the validation rules of a schema that the `FieldType` can't express
+fn parse_constraints(schema: &Schema) -> Vec<Constraint> {
+    let mut constraints = vec![];
+    if let Schema::Def(SchemaDef::Array {
+        min_items,
+        max_items,
+        unique_items,
+        ..
+    }) = schema
+    {
+        constraints.extend(min_items.map(Constraint::MinItems));
+        constraints.extend(max_items.map(Constraint::MaxItems));
+        if *unique_items {
+            constraints.push(Constraint::UniqueItems);
+        }
+    }
+    if let Schema::Def(
+        SchemaDef::String { not: Some(not), .. }
+        | SchemaDef::Integer { not: Some(not), .. }
+        | SchemaDef::Number { not: Some(not), .. },
+    ) = schema
+    {
+        let values = not
+            .const_value
+            .iter()
+            .chain(&not.enum_values)
+            .cloned()
+            .collect();
+        constraints.push(Constraint::Not(values));
     }
+    constraints
 }
 
-fn parse_combinator_schemas(schemas: Vec<Schema>) -> (Vec<String>, Vec<Entity>) {
+/// Parses the `const` of an object schema into a struct where every field is constrained to its
+/// value, nested objects become const structs of their own named `<name><Field>`
+fn parse_const_object(
+    value: serde_json::Value,
+    name: String,
+    ctx: &ParseContext,
+    path: &str,
+) -> Result<Vec<Entity>, GenerationError> {
+    let serde_json::Value::Object(values) = value else {
+        return Err(unsupported(
+            path,
+            "the `const` of an object has to be an object",
+        ));
+    };
+    let mut entities = vec![];
+    let mut properties = IndexMap::new();
+    for (field_name, value) in values {
+        let field_type = if value.is_object() {
+            let mut nested_name = format!("{}{}", name, pascal_case(&field_name));
+            if ctx.schema_names.contains(&nested_name) {
+                nested_name = generate_struct_name();
+            }
+            entities.append(&mut parse_const_object(
+                value,
+                nested_name.clone(),
+                ctx,
+                path,
+            )?);
+            FieldType::Named(nested_name)
+        } else {
+            parse_literal(value, path)?
+        };
+        let field = Field {
+            optional: false,
+            field_type,
+            constraints: vec![],
+            write_only: false,
+            extensions: Default::default(),
+        };
+        properties.insert(field_name, field);
+    }
+    entities.push(Entity {
+        name,
+        def: EntityDef::Struct(StructDef {
+            properties,
+            additional_properties: None,
+            example: None,
+            dependent_required: IndexMap::new(),
+            display: None,
+        }),
+    });
+    Ok(entities)
+}
+
+/// Parses the members of an `allOf` or `oneOf` at `path`, which have to be references, objects
+/// or combinators themselves
+fn parse_combinator_schemas(
+    schemas: Vec<Schema>,
+    ctx: &ParseContext,
+    root: &str,
+    path: &str,
+) -> Result<(Vec<String>, Vec<Entity>), GenerationError> {
     let mut entities = vec![];
     let mut combinator_entities = vec![];
-    for schema in schemas {
+    for (index, schema) in schemas.into_iter().enumerate() {
+        let path = format!("{}/{}", path, index);
         match schema {
+            Schema::Ref(schema_ref) if schema_ref.schema_path == "#" => {
+                combinator_entities.push(root.to_string());
+            }
             Schema::Ref(schema_ref) => {
-                let name = schema_ref.get_schema_name().to_string();
-                combinator_entities.push(name);
+                combinator_entities.push(schema_ref.get_schema_name());
             }
             Schema::Def(schema_def) => {
                 let name = match &schema_def {
@@ -246,49 +789,327 @@ fn parse_combinator_schemas(schemas: Vec<Schema>) -> (Vec<String>, Vec<Entity>)
                     | SchemaDef::AnyOf { ref title, .. } => {
                         title.clone().unwrap_or_else(generate_struct_name)
                     }
-                    _ => panic!(
-                        "Combinator not supposed to have this type of schema inside: {:?}",
-                        schema_def
-                    ),
+                    _ => {
+                        return Err(unsupported(
+                            &path,
+                            "members of a combinator have to be objects or combinators",
+                        ))
+                    }
                 };
 
-                let mut parsed_entities = parse_entity(schema_def, name.clone());
+                let mut parsed_entities = parse_entity(schema_def, name.clone(), ctx, root, &path)?;
                 entities.append(&mut parsed_entities);
                 combinator_entities.push(name);
             }
         }
     }
-    (combinator_entities, entities)
+    Ok((combinator_entities, entities))
+}
+
+/// An `allOf` of string schemas where some of them are `enum`s narrows the type down to the
+/// values all the enums have in common, e.g. `allOf: [{type: string}, {enum: [a, b]}]`
+fn narrowed_enum_values(all_of: &[Schema]) -> Option<Vec<String>> {
+    let mut values: Option<Vec<String>> = None;
+    for schema in all_of {
+        let member_values = match schema {
+            Schema::Def(SchemaDef::String {
+                type_def: PrimitiveType::Enum { enum_values },
+                ..
+            }) => enum_values.clone(),
+            Schema::Def(SchemaDef::Enum { enum_values, .. }) => enum_values
+                .iter()
+                .map(|value| value.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()?,
+            Schema::Def(SchemaDef::String {
+                type_def: PrimitiveType::Basic { .. },
+                ..
+            }) => continue,
+            _ => return None,
+        };
+        values = Some(match values {
+            Some(values) => values
+                .into_iter()
+                .filter(|value| member_values.contains(value))
+                .collect(),
+            None => member_values,
+        });
+    }
+    values
+}
+
+/// An `allOf` of primitive schemas of the same `type` merged into a single schema, e.g.
+/// `allOf: [{type: string}, {type: string, format: uuid}]` is a uuid. `None` if a member isn't a
+/// primitive, an error if they can't be merged, like a `string` and an `integer`.
+fn merged_primitive(all_of: &[Schema], path: &str) -> Option<Result<SchemaDef, GenerationError>> {
+    let mut members = all_of.iter().map(|member| match member {
+        Schema::Def(
+            schema_def @ (SchemaDef::String { .. }
+            | SchemaDef::Integer { .. }
+            | SchemaDef::Number { .. }
+            | SchemaDef::Boolean { .. }),
+        ) => Some(schema_def.clone()),
+        _ => None,
+    });
+    let first = members.next()??;
+    let members = members.collect::<Option<Vec<_>>>()?;
+    Some(members.into_iter().try_fold(first, |merged, member| {
+        merge_primitives(merged, member).ok_or_else(|| {
+            unsupported(
+                path,
+                "the members of an `allOf` have to be of the same `type` with the same `format` and overlapping values",
+            )
+        })
+    }))
+}
+
+/// Two primitive schemas of the same `type` merged into one that satisfies both
+fn merge_primitives(a: SchemaDef, b: SchemaDef) -> Option<SchemaDef> {
+    Some(match (a, b) {
+        (
+            SchemaDef::String {
+                schema_type,
+                type_def,
+                write_only,
+                content_encoding,
+                not,
+                extensions,
+            },
+            SchemaDef::String {
+                type_def: other_type_def,
+                write_only: other_write_only,
+                content_encoding: other_content_encoding,
+                not: other_not,
+                extensions: other_extensions,
+                ..
+            },
+        ) => SchemaDef::String {
+            schema_type,
+            type_def: merge_type_defs(type_def, other_type_def)?,
+            write_only: write_only || other_write_only,
+            content_encoding: merge_option(content_encoding, other_content_encoding)?,
+            not: merge_not(not, other_not),
+            extensions: merge_extensions(extensions, other_extensions),
+        },
+        (
+            SchemaDef::Integer {
+                schema_type,
+                type_def,
+                write_only,
+                not,
+                extensions,
+            },
+            SchemaDef::Integer {
+                type_def: other_type_def,
+                write_only: other_write_only,
+                not: other_not,
+                extensions: other_extensions,
+                ..
+            },
+        ) => SchemaDef::Integer {
+            schema_type,
+            type_def: merge_type_defs(type_def, other_type_def)?,
+            write_only: write_only || other_write_only,
+            not: merge_not(not, other_not),
+            extensions: merge_extensions(extensions, other_extensions),
+        },
+        (
+            SchemaDef::Number {
+                schema_type,
+                type_def,
+                write_only,
+                not,
+                extensions,
+            },
+            SchemaDef::Number {
+                type_def: other_type_def,
+                write_only: other_write_only,
+                not: other_not,
+                extensions: other_extensions,
+                ..
+            },
+        ) => SchemaDef::Number {
+            schema_type,
+            type_def: merge_type_defs(type_def, other_type_def)?,
+            write_only: write_only || other_write_only,
+            not: merge_not(not, other_not),
+            extensions: merge_extensions(extensions, other_extensions),
+        },
+        (boolean @ SchemaDef::Boolean { .. }, SchemaDef::Boolean { .. }) => boolean,
+        _ => return None,
+    })
+}
+
+/// The values both `const`s or `enum`s allow, the larger `minimum` and the `format` of either
+fn merge_type_defs<T: PartialEq + PartialOrd>(
+    a: PrimitiveType<T>,
+    b: PrimitiveType<T>,
+) -> Option<PrimitiveType<T>> {
+    match (a, b) {
+        (
+            PrimitiveType::Basic { format, minimum },
+            PrimitiveType::Basic {
+                format: other_format,
+                minimum: other_minimum,
+            },
+        ) => Some(PrimitiveType::Basic {
+            format: merge_option(format, other_format)?,
+            minimum: match (minimum, other_minimum) {
+                (Some(minimum), Some(other)) if other > minimum => Some(other),
+                (minimum, other) => minimum.or(other),
+            },
+        }),
+        (PrimitiveType::Basic { .. }, values) | (values, PrimitiveType::Basic { .. }) => {
+            Some(values)
+        }
+        (PrimitiveType::Const { const_value }, PrimitiveType::Const { const_value: other }) => {
+            (const_value == other).then_some(PrimitiveType::Const { const_value })
+        }
+        (PrimitiveType::Const { const_value }, PrimitiveType::Enum { enum_values })
+        | (PrimitiveType::Enum { enum_values }, PrimitiveType::Const { const_value }) => {
+            enum_values
+                .contains(&const_value)
+                .then_some(PrimitiveType::Const { const_value })
+        }
+        (
+            PrimitiveType::Enum { enum_values },
+            PrimitiveType::Enum {
+                enum_values: other_values,
+            },
+        ) => {
+            let enum_values = enum_values
+                .into_iter()
+                .filter(|value| other_values.contains(value))
+                .collect::<Vec<_>>();
+            (!enum_values.is_empty()).then_some(PrimitiveType::Enum { enum_values })
+        }
+    }
+}
+
+/// Either value if only one is set, `None` if both are set to different values
+fn merge_option<T: PartialEq>(a: Option<T>, b: Option<T>) -> Option<Option<T>> {
+    match (a, b) {
+        (Some(a), Some(b)) => (a == b).then_some(Some(a)),
+        (a, b) => Some(a.or(b)),
+    }
+}
+
+/// The values excluded by either `not`
+fn merge_not(a: Option<Not>, b: Option<Not>) -> Option<Not> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(Not {
+            const_value: None,
+            enum_values: [a, b]
+                .into_iter()
+                .flat_map(|not| not.const_value.into_iter().chain(not.enum_values))
+                .collect(),
+        }),
+        (a, b) => a.or(b),
+    }
+}
+
+fn merge_extensions(mut a: Extensions, b: Extensions) -> Extensions {
+    a.0.extend(b.0);
+    a
 }
 
 /// Parses a schema type definition into a list of struct definitions
 /// It returns a list because of the inner anonymous types that get generated along the way
 /// The last entry in the Vector is the actual entity being requested to parse, I don't care enough right now
 /// to fix this retarded API, deal with it. (TODO: fix this)
-fn parse_entity(def: SchemaDef, name: String) -> Vec<Entity> {
-    match def {
+fn parse_entity(
+    def: SchemaDef,
+    name: String,
+    ctx: &ParseContext,
+    root: &str,
+    path: &str,
+) -> Result<Vec<Entity>, GenerationError> {
+    Ok(match def {
+        SchemaDef::Object {
+            const_value: Some(const_value),
+            ..
+        } => parse_const_object(const_value, name, ctx, path)?,
+        SchemaDef::Object {
+            properties: None,
+            additional_properties:
+                additional_properties @ (AdditionalProperties::Boolean(true)
+                | AdditionalProperties::Schema(_)),
+            property_names,
+            min_properties,
+            max_properties,
+            ..
+        } => {
+            let (value_type, mut entities) = match additional_properties {
+                AdditionalProperties::Schema(schema) => {
+                    let value_name = format!("{}Value", name);
+                    let path = format!("{}/additionalProperties", path);
+                    let (field_type, entities) =
+                        parse_schema(*schema, ctx, root, Some(&value_name), &path)?;
+                    (Some(field_type), entities)
+                }
+                AdditionalProperties::Boolean(_) => (None, vec![]),
+            };
+            let constraints = property_names
+                .and_then(|property_names| property_names.pattern)
+                .map(Constraint::PropertyNamePattern)
+                .into_iter()
+                .chain(min_properties.map(Constraint::MinProperties))
+                .chain(max_properties.map(Constraint::MaxProperties))
+                .collect();
+            entities.push(Entity {
+                name,
+                def: EntityDef::Map(value_type, constraints),
+            });
+            entities
+        }
         SchemaDef::Object {
             properties,
             required,
             additional_properties,
+            example,
+            examples,
+            dependent_required,
+            extensions,
             ..
         } => {
+            let display = extensions
+                .get("x-display")
+                .and_then(serde_yaml::Value::as_str)
+                .map(str::to_string);
             let mut entities = vec![];
-            let mut struct_properties: HashMap<String, Field> = HashMap::new();
+            let mut struct_properties: IndexMap<String, Field> = IndexMap::new();
             let additional_properties = match additional_properties {
                 AdditionalProperties::Boolean(true) => Some(FieldType::Object(None)),
                 AdditionalProperties::Boolean(false) => None,
                 AdditionalProperties::Schema(schema) => {
-                    let (field_type, mut new_entities) = parse_schema(*schema);
+                    let path = format!("{}/additionalProperties", path);
+                    let (field_type, mut new_entities) =
+                        parse_schema(*schema, ctx, root, None, &path)?;
                     entities.append(&mut new_entities);
                     Some(field_type)
                 }
             };
             for (field_name, field_def) in properties.unwrap_or_default() {
-                let (field_type, mut new_entities) = parse_schema(field_def);
+                let constraints = parse_constraints(&field_def);
+                let write_only = is_write_only(&field_def);
+                let extensions = field_def.extensions().cloned().unwrap_or_default();
+                // a nullable property is generated like an optional one
+                let optional = !required.contains(&field_name) || is_nullable(&field_def);
+                let field_entity_name = format!("{}{}", name, pascal_case(&field_name));
+                let field_path = format!("{}/properties/{}", path, field_name);
+                let (field_type, mut new_entities) =
+                    parse_schema(field_def, ctx, root, Some(&field_entity_name), &field_path)?;
+                let field_type = box_self_references(field_type, &name);
+                for entity in new_entities.iter_mut() {
+                    if let EntityDef::Tuple(members, _) = &mut entity.def {
+                        *members = box_tuple_members(std::mem::take(members), &name);
+                    }
+                }
                 let field = Field {
-                    optional: !required.contains(&field_name),
+                    optional,
                     field_type,
+                    constraints,
+                    write_only,
+                    extensions,
                 };
                 struct_properties.insert(field_name, field);
                 entities.append(&mut new_entities);
@@ -297,6 +1118,9 @@ fn parse_entity(def: SchemaDef, name: String) -> Vec<Entity> {
             let struct_def = StructDef {
                 properties: struct_properties,
                 additional_properties,
+                example: examples.into_iter().next().or(example),
+                dependent_required,
+                display,
             };
             entities.push(Entity {
                 name,
@@ -304,74 +1128,1585 @@ fn parse_entity(def: SchemaDef, name: String) -> Vec<Entity> {
             });
             entities
         }
-        SchemaDef::AllOf { all_of, .. } => {
-            let (all_of_entity_names, mut entities) = parse_combinator_schemas(all_of);
+        SchemaDef::AllOf {
+            mut all_of,
+            properties,
+            required,
+            ..
+        } => {
+            // the schema's own properties become one more member of the composite
+            if let Some(properties) = properties {
+                all_of.push(Schema::Def(SchemaDef::Object {
+                    title: Some(format!("{}Properties", name)),
+                    schema_type: Default::default(),
+                    additional_properties: Default::default(),
+                    properties: Some(properties),
+                    required,
+                    const_value: None,
+                    example: None,
+                    examples: vec![],
+                    property_names: None,
+                    min_properties: None,
+                    max_properties: None,
+                    dependent_required: Default::default(),
+                    extensions: Default::default(),
+                }));
+            }
+            if let Some(values) = narrowed_enum_values(&all_of) {
+                return Ok(vec![Entity {
+                    name,
+                    def: EntityDef::Enum(EnumDef {
+                        values,
+                        names: vec![],
+                        descriptions: vec![],
+                        repr: None,
+                    }),
+                }]);
+            }
+            if let Some(merged) = merged_primitive(&all_of, path) {
+                let (field_type, mut entities) =
+                    parse_schema(Schema::Def(merged?), ctx, root, Some(&name), path)?;
+                entities.push(Entity {
+                    def: EntityDef::Alias(field_type),
+                    name,
+                });
+                return Ok(entities);
+            }
+            let (all_of_entity_names, mut entities) =
+                parse_combinator_schemas(all_of, ctx, root, &format!("{}/allOf", path))?;
             let all_of_def = Entity { def: EntityDef::AllOf(all_of_entity_names), name };
             entities.push(all_of_def);
             entities
 
         },
         SchemaDef::OneOf {
-            one_of,
-            discriminator: discriminant,
+            mut one_of,
+            discriminator,
             ..
         } => {
-            let (variants, mut entities) = parse_combinator_schemas(one_of);
-            let one_of_def = Entity { def: EntityDef::OneOf { discriminant, variants }, name };
+            let variant_tags = one_of
+                .iter_mut()
+                .enumerate()
+                .map(|(index, variant)| {
+                    let tag = discriminator.as_ref()?.tag_for(index, variant)?.to_string();
+                    // inline variants are named after their tag instead of an anonymous name
+                    if let Schema::Def(
+                        SchemaDef::Object { title, .. }
+                        | SchemaDef::AllOf { title, .. }
+                        | SchemaDef::OneOf { title, .. }
+                        | SchemaDef::AnyOf { title, .. },
+                    ) = variant
+                    {
+                        title.get_or_insert_with(|| pascal_case(&tag));
+                    }
+                    Some(tag)
+                })
+                .collect::<Vec<_>>();
+            let (variants, mut entities) =
+                parse_combinator_schemas(one_of, ctx, root, &format!("{}/oneOf", path))?;
+            let tags = variants
+                .iter()
+                .zip(variant_tags)
+                .filter_map(|(variant, tag)| Some((variant.clone(), tag?)))
+                .filter(|(variant, tag)| variant != tag)
+                .collect();
+            let discriminant = discriminator.map(|d| d.property_name().to_string());
+            let one_of_def = Entity { def: EntityDef::OneOf { discriminant, variants, tags }, name };
             entities.push(one_of_def);
             entities
         },
-        SchemaDef::AnyOf { .. } => panic!("AnyOf not supported yet!..."),
-        _ => panic!(
-            "Can't parse this type ({:?}) as an entity, only variants allowed: (AllOf, OneOf, AnyOf, Object)", def
-        ),
-    }
+        SchemaDef::Enum {
+            enum_values,
+            extensions,
+            ..
+        } => {
+            let integers = enum_values
+                .iter()
+                .map(|value| value.as_i64().map(|value| IntegerValue(value.into())))
+                .collect::<Option<Vec<_>>>();
+            let def = if enum_values.iter().all(serde_json::Value::is_string) {
+                let values = enum_values
+                    .into_iter()
+                    .filter_map(|value| value.as_str().map(str::to_string))
+                    .collect::<Vec<_>>();
+                EntityDef::Enum(EnumDef {
+                    names: enum_names(&extensions, values.len()),
+                    descriptions: enum_descriptions(&extensions, values.len()),
+                    values,
+                    repr: None,
+                })
+            } else if let Some(integers) = integers {
+                integer_enum(integers, &extensions)
+            } else {
+                EntityDef::MixedEnum(
+                    enum_values
+                        .into_iter()
+                        .map(|value| parse_literal(value, path))
+                        .collect::<Result<_, _>>()?,
+                )
+            };
+            vec![Entity { name, def }]
+        }
+        SchemaDef::MultiType { types, format, .. }
+            if types.iter().filter(|t| **t != TypeName::Null).count() > 1 =>
+        {
+            let field_types = parse_multi_type(types, format, ctx, root, path)?;
+            vec![Entity {
+                name,
+                def: EntityDef::MixedEnum(field_types),
+            }]
+        }
+        // a schema that's nothing but a `const` is an enum with a single value
+        SchemaDef::String {
+            type_def: PrimitiveType::Const { const_value },
+            ..
+        } => vec![Entity {
+            name,
+            def: EntityDef::MixedEnum(vec![FieldType::Const(Primitive::String, const_value)]),
+        }],
+        SchemaDef::Integer {
+            type_def: PrimitiveType::Const { const_value },
+            ..
+        } => vec![Entity {
+            name,
+            def: EntityDef::MixedEnum(vec![FieldType::Const(
+                Primitive::Long,
+                const_value.to_string(),
+            )]),
+        }],
+        SchemaDef::AnyOf { .. } => return Err(unsupported(path, "`anyOf` can't be generated yet")),
+        _ => return Err(unsupported(
+            path,
+            "only objects, `allOf`, `oneOf`, `enum`s and string or integer `const`s can be generated as a type of their own",
+        )),
+    })
 }
 
 /// Entry point for this module, turns a Mapping of `SchemaDef` into a list of `Entity` that a
-/// generator can consume to generate code. TODO: duplicate struct identifiers cause code to be generated
-/// that won't compile.
-pub fn parse_schema_def_collection(schema: HashMap<String, SchemaDef>) -> Vec<Entity> {
-    schema
-        .into_par_iter()
-        .flat_map(|(name, schema_def)| parse_entity(schema_def, name))
-        .collect::<Vec<_>>()
+/// generator can consume to generate code. The entities are sorted by name and the anonymous ones
+/// renumbered so the generated code doesn't depend on the iteration order of the `HashMap`.
+/// Inline entities of different schemas that end up with the same name, e.g. the `bar` property
+/// of `UserFoo` and the `foo_bar` property of `User`, are told apart by `rename_clashes`.
+pub fn parse_schema_def_collection(
+    schema: HashMap<String, SchemaDef>,
+    ctx: &ParseContext,
+) -> Result<Vec<Entity>, GenerationError> {
+    parse_schema_defs(schema, ctx).map_err(|mut errors| errors.remove(0))
 }
 
-#[cfg(test)]
-mod test {
-    use std::collections::HashMap;
+/// Like `parse_schema_def_collection` but doesn't stop at the first schema it can't parse, every
+/// one of them is reported ordered by name
+pub(crate) fn parse_schema_defs(
+    schema: HashMap<String, SchemaDef>,
+    ctx: &ParseContext,
+) -> Result<Vec<Entity>, Vec<GenerationError>> {
+    let roots = schema.keys().cloned().collect::<Vec<_>>();
+    let ctx = &with_schema_names(ctx, roots.iter().cloned());
+    let mut parsed = map_items(schema, ctx.parallel, |(name, schema_def)| {
+        let root = name.clone();
+        (
+            root.clone(),
+            parse_entity(schema_def, name, ctx, &root, &root),
+        )
+    });
+    parsed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut parsed_roots = vec![];
+    let mut errors = vec![];
+    for (root, result) in parsed {
+        match result {
+            Ok(parsed_entities) => parsed_roots.push((root, parsed_entities)),
+            Err(e) => errors.push(e),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let entities = rename_clashes(parsed_roots).map_err(|e| vec![e])?;
+    let mut entities = tags_from_consts(renumber_anonymous(entities, &roots));
+    entities.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entities)
+}
 
-    use crate::deserializer::SchemaDef;
+/// `ctx` with `names` added to its `schema_names`
+fn with_schema_names(ctx: &ParseContext, names: impl IntoIterator<Item = String>) -> ParseContext {
+    let mut ctx = ctx.clone();
+    ctx.schema_names.extend(names);
+    ctx
+}
 
-    use super::StructDef;
+/// Joins the entities parsed for every root (sorted by root), an inline entity whose name was
+/// already given out to an entity of an earlier root gets an anonymous name. The
+/// names of the roots themselves can't clash, an inline entity with the `title` of one is a
+/// `GenerationError::DuplicateSchema`.
+fn rename_clashes(parsed: Vec<(String, Vec<Entity>)>) -> Result<Vec<Entity>, GenerationError> {
+    let roots = parsed
+        .iter()
+        .map(|(root, _)| root.clone())
+        .collect::<HashSet<_>>();
+    let mut taken = HashSet::new();
+    let mut entities = vec![];
+    for (root, mut root_entities) in parsed {
+        let mut renames = HashMap::new();
+        for entity in &root_entities {
+            if entity.name == root || !taken.contains(&entity.name) {
+                continue;
+            }
+            if roots.contains(&entity.name) {
+                return Err(GenerationError::DuplicateSchema(entity.name.clone()));
+            }
+            let name = match entity.def {
+                EntityDef::Enum(_) | EntityDef::MixedEnum(_) => generate_enum_name(),
+                _ => generate_struct_name(),
+            };
+            renames.insert(entity.name.clone(), name);
+        }
+        rename_entities(&mut root_entities, &renames);
+        taken.extend(root_entities.iter().map(|entity| entity.name.clone()));
+        entities.append(&mut root_entities);
+    }
+    Ok(entities)
+}
 
-    #[test]
-    fn test_parse_schema_with_uint() {
-        let yaml = r#"
-            GetUser:
-              type: object
-              properties:
-                id:
-                  type: integer
-                  minimum: 69
-              required:
-                - id
-        "#;
-        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
-        let entities = super::parse_schema_def_collection(schema_def);
-        match &entities[0].def {
-            super::EntityDef::Struct(StructDef { properties, .. }) => {
-                let id_field = properties.get("id").unwrap();
-                assert!(
-                    matches!(
-                        id_field.field_type,
-                        super::FieldType::Simple(super::Primitive::U64)
-                    ),
-                    "Expected U64 type for id field, due to minimum > 0"
-                );
+/// Tags the variants of discriminated `OneOf`s that the `mapping` doesn't cover with the value
+/// of their discriminant property, if the variant is a struct declaring it as a `const`
+fn tags_from_consts(mut entities: Vec<Entity>) -> Vec<Entity> {
+    let consts = entities
+        .iter()
+        .filter_map(|entity| match &entity.def {
+            EntityDef::Struct(StructDef { properties, .. }) => Some(
+                properties
+                    .iter()
+                    .filter_map(|(property, field)| match &field.field_type {
+                        FieldType::Const(_, value) => {
+                            Some(((entity.name.clone(), property.clone()), value.clone()))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        })
+        .flatten()
+        .collect::<HashMap<_, _>>();
+    for entity in &mut entities {
+        if let EntityDef::OneOf {
+            discriminant: Some(discriminant),
+            variants,
+            tags,
+        } = &mut entity.def
+        {
+            for variant in variants.iter() {
+                if tags.contains_key(variant) {
+                    continue;
+                }
+                match consts.get(&(variant.clone(), discriminant.clone())) {
+                    Some(value) if value != variant => {
+                        tags.insert(variant.clone(), value.clone());
+                    }
+                    _ => {}
+                }
             }
-            _ => panic!("Expected a Struct definition"),
         }
     }
+    entities
+}
+
+/// Numbers the anonymous entities of every kind from 1 in the order they're found walking the
+/// schemas from `roots`, the counters hand out numbers in whatever order the parallel parsing
+/// happens to get to them
+fn renumber_anonymous(entities: Vec<Entity>, roots: &[String]) -> Vec<Entity> {
+    renumber_anonymous_after(entities, roots, HashMap::new())
+}
+
+/// The prefix of an anonymous entity's name, `None` for an entity that has a name of its own
+fn anonymous_prefix(name: &str) -> Option<&'static str> {
+    ["AnonymousEntity", "AnonymousEnum", "AnonymousTuple"]
+        .into_iter()
+        .find(|prefix| {
+            name.strip_prefix(prefix).is_some_and(|number| {
+                !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+            })
+        })
+}
+
+/// Like `renumber_anonymous` but numbering every kind on from its count in `counts`
+fn renumber_anonymous_after(
+    mut entities: Vec<Entity>,
+    roots: &[String],
+    mut counts: HashMap<&'static str, usize>,
+) -> Vec<Entity> {
+    let renames = entity_sources(&entities, roots, "")
+        .into_iter()
+        .filter_map(|(_, name)| {
+            let prefix = anonymous_prefix(&name)?;
+            let count = counts.entry(prefix).or_insert(0);
+            *count += 1;
+            Some((name, format!("{}{}", prefix, count)))
+        })
+        .collect::<HashMap<_, _>>();
+    rename_entities(&mut entities, &renames);
+    entities
+}
+
+/// Renames the entities in `renames` (old name to new name) together with every reference to
+/// them, e.g. for a transform passed to `generate_rust_with_transform`
+pub fn rename_entities(entities: &mut [Entity], renames: &HashMap<String, String>) {
+    let rename = |name: &mut String| {
+        if let Some(renamed) = renames.get(name) {
+            *name = renamed.clone();
+        }
+    };
+    fn rename_field_type(field_type: &mut FieldType, rename: &impl Fn(&mut String)) {
+        match field_type {
+            FieldType::Named(name) => rename(name),
+            FieldType::Array(Some(inner))
+            | FieldType::Object(Some(inner))
+            | FieldType::Boxed(inner) => rename_field_type(inner, rename),
+            FieldType::Tuple(members) => members
+                .iter_mut()
+                .for_each(|member| rename_field_type(member, rename)),
+            _ => {}
+        }
+    }
+    for entity in entities {
+        rename(&mut entity.name);
+        match &mut entity.def {
+            EntityDef::Struct(StructDef {
+                properties,
+                additional_properties,
+                ..
+            }) => {
+                properties
+                    .values_mut()
+                    .for_each(|field| rename_field_type(&mut field.field_type, &rename));
+                if let Some(field_type) = additional_properties {
+                    rename_field_type(field_type, &rename);
+                }
+            }
+            EntityDef::OneOf { variants, tags, .. } => {
+                variants.iter_mut().for_each(rename);
+                *tags = std::mem::take(tags)
+                    .into_iter()
+                    .map(|(mut variant, tag)| {
+                        rename(&mut variant);
+                        (variant, tag)
+                    })
+                    .collect();
+            }
+            EntityDef::AllOf(members) => members.iter_mut().for_each(rename),
+            EntityDef::Tuple(members, tail) => members
+                .iter_mut()
+                .chain(tail)
+                .for_each(|member| rename_field_type(member, &rename)),
+            EntityDef::MixedEnum(members) => members
+                .iter_mut()
+                .for_each(|member| rename_field_type(member, &rename)),
+            EntityDef::Map(Some(field_type), _) => rename_field_type(field_type, &rename),
+            EntityDef::Alias(target) => rename_field_type(target, &rename),
+            EntityDef::Map(None, _) | EntityDef::Enum(_) => {}
+        }
+    }
+}
+
+/// Checks that every name in the `required` of an object (nested ones included) is one of its
+/// `properties`. Objects without `properties` are left alone, their keys aren't known.
+pub(crate) fn check_required(schemas: &HashMap<String, SchemaDef>) -> Result<(), GenerationError> {
+    let mut names = schemas.keys().collect::<Vec<_>>();
+    names.sort();
+    names
+        .into_iter()
+        .try_for_each(|name| check_required_in(&schemas[name], name))
+}
+
+/// `check_required` for the inline payloads of `messages`, a `$ref` payload is checked with the
+/// schemas
+pub(crate) fn check_required_messages(
+    messages: &HashMap<String, MessageDef>,
+) -> Result<(), GenerationError> {
+    let mut names = messages.keys().collect::<Vec<_>>();
+    names.sort();
+    names
+        .into_iter()
+        .try_for_each(|name| match &messages[name].payload {
+            Some(Schema::Def(schema_def)) => check_required_in(schema_def, name),
+            _ => Ok(()),
+        })
+}
+
+fn check_required_in(schema_def: &SchemaDef, path: &str) -> Result<(), GenerationError> {
+    fn indexed<'a>(path: &str, keyword: &str, schemas: &'a [Schema]) -> Vec<(String, &'a Schema)> {
+        schemas
+            .iter()
+            .enumerate()
+            .map(|(index, schema)| (format!("{}/{}/{}", path, keyword, index), schema))
+            .collect()
+    }
+    let nested = match schema_def {
+        SchemaDef::Object {
+            properties,
+            required,
+            additional_properties,
+            ..
+        } => {
+            if let Some(properties) = properties {
+                if let Some(unknown) = required.iter().find(|name| !properties.contains_key(*name))
+                {
+                    return Err(GenerationError::UnknownRequiredProperty(
+                        path.to_string(),
+                        unknown.clone(),
+                    ));
+                }
+            }
+            let mut nested = properties
+                .iter()
+                .flatten()
+                .map(|(name, schema)| (format!("{}/properties/{}", path, name), schema))
+                .collect::<Vec<_>>();
+            if let AdditionalProperties::Schema(schema) = additional_properties {
+                nested.push((format!("{}/additionalProperties", path), schema));
+            }
+            nested
+        }
+        SchemaDef::Array {
+            items: Some(items), ..
+        } => vec![(format!("{}/items", path), items.as_ref())],
+        SchemaDef::Tuple {
+            prefix_items,
+            items,
+            ..
+        } => {
+            let mut nested = indexed(path, "prefixItems", prefix_items);
+            if let TupleItems::Tail(items) = items {
+                nested.push((format!("{}/items", path), items.as_ref()));
+            }
+            nested
+        }
+        // `required` may list properties of the members, only the own properties are checked
+        SchemaDef::AllOf {
+            all_of, properties, ..
+        } => {
+            let mut nested = indexed(path, "allOf", all_of);
+            nested.extend(
+                properties
+                    .iter()
+                    .flatten()
+                    .map(|(name, schema)| (format!("{}/properties/{}", path, name), schema)),
+            );
+            nested
+        }
+        SchemaDef::OneOf { one_of, .. } => indexed(path, "oneOf", one_of),
+        SchemaDef::AnyOf { any_of, .. } => indexed(path, "anyOf", any_of),
+        _ => vec![],
+    };
+    nested
+        .into_iter()
+        .try_for_each(|(path, schema)| match schema {
+            Schema::Def(schema_def) => check_required_in(schema_def, &path),
+            Schema::Ref(_) => Ok(()),
+        })
+}
+
+/// Pairs the JSON pointer of the schema every entity was generated from with the entity's name,
+/// e.g. `#/components/schemas/User/properties/address` -> `UserAddress`. Nested entities are
+/// found by following the references of the top-level schemas `roots` (the keys of the mapping
+/// at `components_pointer`).
+pub fn entity_sources(
+    entities: &[Entity],
+    roots: &[String],
+    components_pointer: &str,
+) -> Vec<(String, String)> {
+    let definitions = entities
+        .iter()
+        .map(|entity| (entity.name.as_str(), &entity.def))
+        .collect::<HashMap<_, _>>();
+    let mut sources = SourceWalk {
+        definitions,
+        roots,
+        visited: HashSet::new(),
+        sources: vec![],
+    };
+    let mut roots = roots.iter().collect::<Vec<_>>();
+    roots.sort();
+    for root in roots {
+        let pointer = format!("#{}/{}", components_pointer, escape_pointer(root));
+        sources.visit_entity(root, pointer);
+    }
+    sources.sources
+}
+
+/// `~` and `/` are the only characters that need escaping in a JSON pointer segment
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+struct SourceWalk<'a> {
+    definitions: HashMap<&'a str, &'a EntityDef>,
+    roots: &'a [String],
+    visited: HashSet<String>,
+    sources: Vec<(String, String)>,
+}
+
+impl SourceWalk<'_> {
+    fn visit_entity(&mut self, name: &str, pointer: String) {
+        let Some(def) = self.definitions.get(name).copied() else {
+            return;
+        };
+        if !self.visited.insert(name.to_string()) {
+            return;
+        }
+        self.sources.push((pointer.clone(), name.to_string()));
+        match def {
+            EntityDef::Struct(StructDef {
+                properties,
+                additional_properties,
+                ..
+            }) => {
+                for (property, field) in properties {
+                    let property = format!("{}/properties/{}", pointer, escape_pointer(property));
+                    self.visit_field_type(&field.field_type, property);
+                }
+                if let Some(field_type) = additional_properties {
+                    self.visit_field_type(field_type, format!("{}/additionalProperties", pointer));
+                }
+            }
+            EntityDef::OneOf { variants, .. } => {
+                for (index, variant) in variants.iter().enumerate() {
+                    self.visit_reference(variant, format!("{}/oneOf/{}", pointer, index));
+                }
+            }
+            EntityDef::AllOf(members) => {
+                for (index, member) in members.iter().enumerate() {
+                    self.visit_reference(member, format!("{}/allOf/{}", pointer, index));
+                }
+            }
+            EntityDef::Tuple(members, tail) => {
+                for (index, member) in members.iter().enumerate() {
+                    self.visit_field_type(member, format!("{}/prefixItems/{}", pointer, index));
+                }
+                if let Some(tail) = tail {
+                    self.visit_field_type(tail, format!("{}/items", pointer));
+                }
+            }
+            EntityDef::Map(Some(field_type), _) => {
+                self.visit_field_type(field_type, format!("{}/additionalProperties", pointer));
+            }
+            EntityDef::Alias(target) => self.visit_field_type(target, pointer),
+            EntityDef::Map(None, _) | EntityDef::Enum(_) | EntityDef::MixedEnum(_) => {}
+        }
+    }
+
+    /// Top-level schemas are visited with their own pointer
+    fn visit_reference(&mut self, name: &str, pointer: String) {
+        if !self.roots.iter().any(|root| root == name) {
+            self.visit_entity(name, pointer);
+        }
+    }
+
+    fn visit_field_type(&mut self, field_type: &FieldType, pointer: String) {
+        match field_type {
+            FieldType::Named(name) => self.visit_reference(name, pointer),
+            FieldType::Array(Some(item_type)) => {
+                self.visit_field_type(item_type, format!("{}/items", pointer))
+            }
+            FieldType::Object(Some(value_type)) => {
+                self.visit_field_type(value_type, format!("{}/additionalProperties", pointer))
+            }
+            FieldType::Tuple(members) => {
+                for (index, member) in members.iter().enumerate() {
+                    self.visit_field_type(member, format!("{}/prefixItems/{}", pointer, index));
+                }
+            }
+            FieldType::Boxed(inner) => self.visit_field_type(inner, pointer),
+            _ => {}
+        }
+    }
+}
+
+/// Whether a payload is only a field type and not a type of its own, e.g. an array or a string
+fn is_field_type_only(schema_def: &SchemaDef) -> bool {
+    match schema_def {
+        SchemaDef::Array { .. }
+        | SchemaDef::Tuple { .. }
+        | SchemaDef::Boolean { .. }
+        | SchemaDef::Number { .. } => true,
+        SchemaDef::String { type_def, .. } => !matches!(type_def, PrimitiveType::Const { .. }),
+        SchemaDef::Integer { type_def, .. } => !matches!(type_def, PrimitiveType::Const { .. }),
+        SchemaDef::MultiType { types, .. } => {
+            types.iter().filter(|t| **t != TypeName::Null).count() <= 1
+        }
+        _ => false,
+    }
+}
+
+/// Turns the messages of an asyncapi document into entities named after the message, inline
+/// payloads are parsed like any top-level schema while `$ref` payloads and those that are only a
+/// field type (e.g. `type: array`) become an alias of their type. Sorted by name like
+/// `parse_schema_def_collection`, anonymous entities are numbered on from those in `schemas`
+/// (the entities of the document's schemas) so their names don't clash.
+pub fn parse_message_collection(
+    messages: HashMap<String, MessageDef>,
+    schemas: &[Entity],
+    ctx: &ParseContext,
+) -> Result<Vec<Entity>, GenerationError> {
+    let roots = messages.keys().cloned().collect::<Vec<_>>();
+    let schema_names = schemas.iter().map(|entity| entity.name.clone());
+    let ctx = &with_schema_names(ctx, schema_names.chain(roots.iter().cloned()));
+    let mut parsed = map_items(messages, ctx.parallel, |(name, message)| {
+        (name.clone(), parse_message(name, message, ctx))
+    });
+    parsed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let parsed = parsed
+        .into_iter()
+        .map(|(root, result)| result.map(|entities| (root, entities)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let entities = rename_clashes(parsed)?;
+    let mut counts = HashMap::new();
+    for entity in schemas {
+        if let Some(prefix) = anonymous_prefix(&entity.name) {
+            let number = entity.name[prefix.len()..].parse().unwrap_or(0);
+            let count = counts.entry(prefix).or_insert(0);
+            *count = number.max(*count);
+        }
+    }
+    let mut entities = renumber_anonymous_after(entities, &roots, counts);
+    entities.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entities)
+}
+
+/// The entities of a single message, see `parse_message_collection`
+fn parse_message(
+    name: String,
+    message: MessageDef,
+    ctx: &ParseContext,
+) -> Result<Vec<Entity>, GenerationError> {
+    match message.payload {
+        Some(Schema::Def(schema_def)) if is_field_type_only(&schema_def) => {
+            // an inline object in the items can't have the name of the alias
+            let item_name = format!("{}Item", name);
+            let (field_type, mut entities) =
+                parse_schema(Schema::Def(schema_def), ctx, &name, Some(&item_name), &name)?;
+            entities.push(Entity {
+                def: EntityDef::Alias(field_type),
+                name,
+            });
+            Ok(entities)
+        }
+        Some(Schema::Def(schema_def)) => {
+            let root = name.clone();
+            parse_entity(schema_def, name, ctx, &root, &root)
+        }
+        Some(Schema::Ref(schema_ref)) if schema_ref.get_schema_name() != name => Ok(vec![Entity {
+            def: EntityDef::Alias(FieldType::Named(schema_ref.get_schema_name())),
+            name,
+        }]),
+        _ => Ok(vec![]),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::deserializer::SchemaDef;
+
+    use super::StructDef;
+
+    #[test]
+    fn test_parse_schema_with_uint() {
+        let yaml = r#"
+            GetUser:
+              type: object
+              properties:
+                id:
+                  type: integer
+                  minimum: 69
+              required:
+                - id
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                let id_field = properties.get("id").unwrap();
+                assert!(
+                    matches!(
+                        id_field.field_type,
+                        super::FieldType::Simple(super::Primitive::U64)
+                    ),
+                    "Expected U64 type for id field, due to minimum > 0"
+                );
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_recursive_tuple() {
+        let yaml = r#"
+            Node:
+              type: object
+              properties:
+                link:
+                  type: array
+                  items: false
+                  prefixItems:
+                    - type: string
+                    - $ref: '#/components/schemas/Node'
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                let link_field = properties.get("link").unwrap();
+                match &link_field.field_type {
+                    super::FieldType::Tuple(members) => {
+                        assert!(matches!(
+                            members[0],
+                            super::FieldType::Simple(super::Primitive::String)
+                        ));
+                        assert!(
+                            matches!(&members[1], super::FieldType::Boxed(inner) if matches!(inner.as_ref(), super::FieldType::Named(name) if name == "Node")),
+                            "Expected the self reference to be boxed"
+                        );
+                    }
+                    _ => panic!("Expected a Tuple field type"),
+                }
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_large_tuple() {
+        let yaml = r#"
+            Wide:
+              type: object
+              properties:
+                values:
+                  type: array
+                  items: false
+                  prefixItems:
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: string
+                    - type: boolean
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        assert_eq!(entities.len(), 2);
+        match &entities[0].def {
+            super::EntityDef::Tuple(members, _) => assert_eq!(members.len(), 13),
+            _ => panic!("Expected a Tuple definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_tuple_with_tail() {
+        let yaml = r#"
+            Command:
+              type: object
+              properties:
+                args:
+                  type: array
+                  prefixItems:
+                    - type: string
+                  items:
+                    type: integer
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        assert_eq!(entities.len(), 2);
+        match &entities[0].def {
+            super::EntityDef::Tuple(
+                members,
+                Some(super::FieldType::Simple(super::Primitive::Int)),
+            ) => {
+                assert_eq!(members.len(), 1)
+            }
+            _ => panic!("Expected a Tuple definition with a tail"),
+        }
+    }
+
+    #[test]
+    fn test_parse_array_constraints() {
+        let yaml = r#"
+            Tagged:
+              type: object
+              properties:
+                tags:
+                  type: array
+                  items:
+                    type: string
+                  minItems: 1
+                  maxItems: 3
+                  uniqueItems: true
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                assert_eq!(
+                    properties.get("tags").unwrap().constraints,
+                    vec![
+                        super::Constraint::MinItems(1),
+                        super::Constraint::MaxItems(3),
+                        super::Constraint::UniqueItems
+                    ]
+                );
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_all_of_enum() {
+        let yaml = r#"
+            Color:
+              allOf:
+                - type: string
+                - enum: [red, green, blue]
+                - type: string
+                  enum: [green, blue, purple]
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        assert_eq!(entities.len(), 1);
+        match &entities[0].def {
+            super::EntityDef::Enum(super::EnumDef { values, .. }) => {
+                assert_eq!(values, &["green", "blue"])
+            }
+            _ => panic!("Expected an Enum definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_all_of_primitives() {
+        let yaml = r#"
+            UserId:
+              allOf:
+                - type: string
+                - type: string
+                  format: uuid
+            Count:
+              allOf:
+                - type: integer
+                  format: int32
+                - type: integer
+                  minimum: 0
+            Mismatch:
+              allOf:
+                - type: string
+                - type: integer
+        "#;
+        let mut schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let mismatch = schema_def.remove("Mismatch").unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        assert!(matches!(
+            &entities[0],
+            super::Entity { name, def: super::EntityDef::Alias(super::FieldType::Simple(super::Primitive::U32)) } if name == "Count"
+        ));
+        assert!(matches!(
+            &entities[1],
+            super::Entity { name, def: super::EntityDef::Alias(super::FieldType::Simple(super::Primitive::Uuid)) } if name == "UserId"
+        ));
+        let schema_def = HashMap::from([("Mismatch".to_string(), mismatch)]);
+        match super::parse_schema_def_collection(schema_def, &Default::default()) {
+            Err(crate::GenerationError::Unsupported(path, _)) => assert_eq!(path, "Mismatch"),
+            other => panic!("Expected an Unsupported error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_inline_name_clash() {
+        let yaml = r#"
+            User:
+              type: object
+              properties:
+                address:
+                  type: object
+                  properties:
+                    street:
+                      type: string
+                foo_bar:
+                  type: object
+                  properties:
+                    baz:
+                      type: string
+            UserAddress:
+              type: object
+              properties:
+                id:
+                  type: string
+            UserFoo:
+              type: object
+              properties:
+                bar:
+                  type: object
+                  properties:
+                    qux:
+                      type: string
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        let names = entities
+            .iter()
+            .map(|entity| entity.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "AnonymousEntity1",
+                "AnonymousEntity2",
+                "User",
+                "UserAddress",
+                "UserFoo",
+                "UserFooBar"
+            ]
+        );
+        // the first root keeps the name, the top-level schema keeps its own
+        let fields = |name: &str| match &entities.iter().find(|e| e.name == name).unwrap().def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                properties.keys().cloned().collect::<Vec<_>>()
+            }
+            _ => panic!("Expected a Struct definition"),
+        };
+        assert_eq!(fields("UserAddress"), ["id"]);
+        assert_eq!(fields("UserFooBar"), ["baz"]);
+        assert_eq!(fields("AnonymousEntity1"), ["street"]);
+        assert_eq!(fields("AnonymousEntity2"), ["qux"]);
+    }
+
+    #[test]
+    fn test_parse_all_of_with_properties() {
+        let yaml = r#"
+            Manager:
+              type: object
+              allOf:
+                - $ref: '#/components/schemas/Person'
+              properties:
+                reports:
+                  type: integer
+              required:
+                - reports
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        assert_eq!(entities.len(), 2);
+        match &entities[0].def {
+            super::EntityDef::AllOf(members) => {
+                assert_eq!(members, &["Person", "ManagerProperties"])
+            }
+            _ => panic!("Expected an AllOf definition"),
+        }
+        match &entities[1].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                assert!(!properties["reports"].optional)
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_integer_formats() {
+        let yaml = r#"
+            Sizes:
+              type: object
+              properties:
+                tiny:
+                  type: integer
+                  format: int8
+                small:
+                  type: integer
+                  format: uint16
+                huge:
+                  type: integer
+                  format: int256
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                assert!(matches!(
+                    properties.get("tiny").unwrap().field_type,
+                    super::FieldType::Simple(super::Primitive::I8)
+                ));
+                assert!(matches!(
+                    properties.get("small").unwrap().field_type,
+                    super::FieldType::Simple(super::Primitive::U16)
+                ));
+                assert!(matches!(
+                    properties.get("huge").unwrap().field_type,
+                    super::FieldType::Simple(super::Primitive::Int)
+                ));
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_unknown_format() {
+        let yaml = r#"
+            Price:
+              type: object
+              properties:
+                amount:
+                  type: number
+                  format: currency
+                code:
+                  type: string
+                  format: iso-4217
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                assert!(matches!(
+                    properties.get("amount").unwrap().field_type,
+                    super::FieldType::Simple(super::Primitive::Double)
+                ));
+                assert!(matches!(
+                    properties.get("code").unwrap().field_type,
+                    super::FieldType::Simple(super::Primitive::String)
+                ));
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+        let format = serde_yaml::from_str::<super::Format>("currency").unwrap();
+        assert!(matches!(format, super::Format::Other(name) if name == "currency"));
+    }
+
+    #[test]
+    fn test_parse_single_value_enum() {
+        let yaml = r#"
+            Order:
+              type: object
+              properties:
+                kind:
+                  type: string
+                  enum: [fixed]
+                version:
+                  type: integer
+                  enum: [2]
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        assert_eq!(entities.len(), 1);
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                assert!(matches!(
+                    &properties.get("kind").unwrap().field_type,
+                    super::FieldType::Const(super::Primitive::String, value) if value == "fixed"
+                ));
+                assert!(matches!(
+                    &properties.get("version").unwrap().field_type,
+                    super::FieldType::Const(super::Primitive::Long, value) if value == "2"
+                ));
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_number_without_format() {
+        let yaml = r#"
+            Reading:
+              type: object
+              properties:
+                value:
+                  type: number
+                  minimum: -1.0e300
+                  maximum: 1.0e300
+                ratio:
+                  type: number
+                  format: float
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                assert!(matches!(
+                    properties.get("value").unwrap().field_type,
+                    super::FieldType::Simple(super::Primitive::Double)
+                ));
+                assert!(matches!(
+                    properties.get("ratio").unwrap().field_type,
+                    super::FieldType::Simple(super::Primitive::Float)
+                ));
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_not() {
+        let yaml = r#"
+            User:
+              type: object
+              properties:
+                name:
+                  type: string
+                  not:
+                    const: forbidden
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => assert_eq!(
+                properties.get("name").unwrap().constraints,
+                [super::Constraint::Not(vec!["forbidden".into()])]
+            ),
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_content_encoding() {
+        let yaml = r#"
+            Upload:
+              type: object
+              properties:
+                data:
+                  type: string
+                  contentEncoding: base64
+                name:
+                  type: string
+                  contentEncoding: 7bit
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                assert!(matches!(
+                    properties.get("data").unwrap().field_type,
+                    super::FieldType::Simple(super::Primitive::Bytes)
+                ));
+                assert!(matches!(
+                    properties.get("name").unwrap().field_type,
+                    super::FieldType::Simple(super::Primitive::String)
+                ));
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_parse_decimal() {
+        let yaml = r#"
+            Payment:
+              type: object
+              properties:
+                amount:
+                  type: string
+                  format: decimal
+                fee:
+                  type: number
+                  format: decimal
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                for field in properties.values() {
+                    assert!(matches!(
+                        field.field_type,
+                        super::FieldType::Simple(super::Primitive::Decimal)
+                    ));
+                }
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_inline_object_named_after_field() {
+        let yaml = r#"
+            User:
+              type: object
+              properties:
+                address:
+                  type: object
+                  properties:
+                    street:
+                      type: string
+                    geo:
+                      type: object
+                      properties:
+                        lat:
+                          type: number
+                previous_addresses:
+                  type: array
+                  items:
+                    type: object
+                    properties:
+                      street:
+                        type: string
+                billing:
+                  type: object
+                  title: BillingInfo
+                  properties:
+                    iban:
+                      type: string
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        let names = entities
+            .iter()
+            .map(|entity| entity.name.as_str())
+            .collect::<Vec<_>>();
+        for name in [
+            "User",
+            "UserAddress",
+            "UserAddressGeo",
+            "UserPreviousAddresses",
+            "BillingInfo",
+        ] {
+            assert!(names.contains(&name), "{} missing in {:?}", name, names);
+        }
+        let user = entities.iter().find(|e| e.name == "User").unwrap();
+        match &user.def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => assert!(matches!(
+                &properties.get("address").unwrap().field_type,
+                super::FieldType::Named(name) if name == "UserAddress"
+            )),
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!(super::pascal_case("home_address"), "HomeAddress");
+        assert_eq!(super::pascal_case("user/signed-up"), "UserSignedUp");
+        assert_eq!(super::pascal_case("{userId}.events"), "UserIdEvents");
+    }
+
+    #[test]
+    fn test_parse_top_level_map() {
+        let yaml = r#"
+            Scores:
+              type: object
+              additionalProperties:
+                type: integer
+            Settings:
+              type: object
+              additionalProperties:
+                type: object
+                properties:
+                  enabled:
+                    type: boolean
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        let scores = entities.iter().find(|e| e.name == "Scores").unwrap();
+        assert!(matches!(
+            scores.def,
+            super::EntityDef::Map(Some(super::FieldType::Simple(super::Primitive::Int)), _)
+        ));
+        let settings = entities.iter().find(|e| e.name == "Settings").unwrap();
+        assert!(matches!(
+            &settings.def,
+            super::EntityDef::Map(Some(super::FieldType::Named(name)), _) if name == "SettingsValue"
+        ));
+        assert!(entities.iter().any(|e| e.name == "SettingsValue"));
+    }
+
+    #[test]
+    fn test_parse_property_names() {
+        let yaml = r#"
+            Labels:
+              type: object
+              additionalProperties:
+                type: string
+              propertyNames:
+                pattern: "^[a-z]+$"
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Map(_, constraints) => assert!(matches!(
+                constraints.as_slice(),
+                [super::Constraint::PropertyNamePattern(pattern)] if pattern == "^[a-z]+$"
+            )),
+            _ => panic!("Expected a Map definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_one_of_tags_from_consts() {
+        let yaml = r#"
+            Request:
+              oneOf:
+                - $ref: '#/components/schemas/GetUser'
+                - $ref: '#/components/schemas/DeleteUser'
+              discriminator:
+                propertyName: event
+                mapping:
+                  removeUser: '#/components/schemas/DeleteUser'
+            GetUser:
+              type: object
+              properties:
+                event:
+                  type: string
+                  const: getUser
+            DeleteUser:
+              type: object
+              properties:
+                event:
+                  type: string
+                  const: deleteUser
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        let request = entities.iter().find(|e| e.name == "Request").unwrap();
+        match &request.def {
+            super::EntityDef::OneOf { tags, .. } => {
+                assert_eq!(tags["GetUser"], "getUser");
+                // the mapping takes precedence
+                assert_eq!(tags["DeleteUser"], "removeUser");
+            }
+            _ => panic!("Expected a OneOf definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_one_of_discriminator_mapping() {
+        let yaml = r#"
+            Request:
+              oneOf:
+                - type: object
+                  properties:
+                    userId:
+                      type: string
+                - type: object
+                  properties:
+                    userId:
+                      type: string
+                - $ref: '#/components/schemas/Ping'
+              discriminator:
+                propertyName: action
+                mapping:
+                  getUser: '#/components/schemas/Request/oneOf/0'
+                  DeleteUser: '#/components/schemas/Request/oneOf/1'
+                  ping: '#/components/schemas/Ping'
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        let request = entities.iter().find(|e| e.name == "Request").unwrap();
+        match &request.def {
+            super::EntityDef::OneOf {
+                discriminant,
+                variants,
+                tags,
+            } => {
+                assert_eq!(discriminant.as_deref(), Some("action"));
+                assert_eq!(variants, &["GetUser", "DeleteUser", "Ping"]);
+                assert_eq!(tags.len(), 2);
+                assert_eq!(tags["GetUser"], "getUser");
+                assert_eq!(tags["Ping"], "ping");
+            }
+            _ => panic!("Expected a OneOf definition"),
+        }
+        assert!(entities.iter().any(|e| e.name == "GetUser"));
+        assert!(entities.iter().any(|e| e.name == "DeleteUser"));
+    }
+
+    #[test]
+    fn test_parse_root_ref() {
+        let yaml = r#"
+            Category:
+              type: object
+              properties:
+                parent:
+                  $ref: '#'
+                children:
+                  type: array
+                  items:
+                    $ref: '#'
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        match &entities[0].def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                assert!(matches!(
+                    &properties.get("parent").unwrap().field_type,
+                    super::FieldType::Boxed(inner)
+                        if matches!(inner.as_ref(), super::FieldType::Named(name) if name == "Category")
+                ));
+                assert!(matches!(
+                    &properties.get("children").unwrap().field_type,
+                    super::FieldType::Array(Some(_))
+                ));
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_x_nullable() {
+        let parse = |label: &str| {
+            let yaml = format!(
+                r#"
+                Item:
+                  type: object
+                  properties:
+                    label:
+                      {}
+                  required:
+                    - label
+                "#,
+                label
+            );
+            let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(&yaml).unwrap();
+            let entities =
+                super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+            match entities.into_iter().next().map(|entity| entity.def) {
+                Some(super::EntityDef::Struct(StructDef { properties, .. })) => {
+                    properties.get("label").unwrap().clone()
+                }
+                other => panic!("Expected a Struct definition, got {:?}", other),
+            }
+        };
+        for label in [
+            "{ type: string, x-nullable: true }",
+            r#"{ type: [string, "null"] }"#,
+        ] {
+            let label = parse(label);
+            assert!(label.optional);
+            assert!(matches!(
+                label.field_type,
+                super::FieldType::Simple(super::Primitive::String)
+            ));
+        }
+        assert!(!parse("{ type: string, x-nullable: false }").optional);
+    }
+
+    #[test]
+    fn test_parse_type_array() {
+        let yaml = r#"
+            Item:
+              type: object
+              properties:
+                label:
+                  type: [string, "null"]
+                value:
+                  type: [string, integer]
+              required:
+                - label
+                - value
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        let value = entities.iter().find(|e| e.name == "ItemValue").unwrap();
+        match &value.def {
+            super::EntityDef::MixedEnum(values) => {
+                assert!(matches!(
+                    values.as_slice(),
+                    [
+                        super::FieldType::Simple(super::Primitive::Int),
+                        super::FieldType::Simple(super::Primitive::String)
+                    ]
+                ));
+            }
+            _ => panic!("Expected a MixedEnum definition"),
+        }
+        let item = entities.iter().find(|e| e.name == "Item").unwrap();
+        match &item.def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                let label = properties.get("label").unwrap();
+                assert!(label.optional);
+                assert!(matches!(
+                    label.field_type,
+                    super::FieldType::Simple(super::Primitive::String)
+                ));
+                let value = properties.get("value").unwrap();
+                assert!(!value.optional);
+                assert!(
+                    matches!(&value.field_type, super::FieldType::Named(name) if name == "ItemValue")
+                );
+            }
+            _ => panic!("Expected a Struct definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_integer_const_beyond_i64() {
+        let yaml = r#"
+            Limits:
+              type: object
+              properties:
+                max:
+                  type: integer
+                  const: 18446744073709551615
+                level:
+                  type: integer
+                  enum: [-1, 18446744073709551615]
+              required:
+                - max
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let entities = super::parse_schema_def_collection(schema_def, &Default::default()).unwrap();
+        let limits = entities.iter().find(|e| e.name == "Limits").unwrap();
+        let level = match &limits.def {
+            super::EntityDef::Struct(StructDef { properties, .. }) => {
+                assert!(matches!(
+                    &properties.get("max").unwrap().field_type,
+                    super::FieldType::Const(super::Primitive::Long, value) if value == "18446744073709551615"
+                ));
+                match &properties.get("level").unwrap().field_type {
+                    super::FieldType::Named(name) => name.clone(),
+                    _ => panic!("Expected a Named field type"),
+                }
+            }
+            _ => panic!("Expected a Struct definition"),
+        };
+        let level = entities.iter().find(|e| e.name == level).unwrap();
+        match &level.def {
+            super::EntityDef::MixedEnum(values) => {
+                assert!(matches!(
+                    values.as_slice(),
+                    [
+                        super::FieldType::Const(super::Primitive::Long, min),
+                        super::FieldType::Const(super::Primitive::Long, max)
+                    ] if min == "-1" && max == "18446744073709551615"
+                ));
+            }
+            _ => panic!("Expected a MixedEnum definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_unsupported_object_const() {
+        let yaml = r#"
+            Settings:
+              type: object
+              properties:
+                scale:
+                  type: object
+                  const:
+                    ratio: 1.5
+                    tags: [a]
+            Level:
+              type: object
+              const: 5
+        "#;
+        let schema_def = serde_yaml::from_str::<HashMap<String, SchemaDef>>(yaml).unwrap();
+        let errors = super::parse_schema_defs(schema_def, &Default::default()).unwrap_err();
+        let paths = errors
+            .iter()
+            .map(|error| match error {
+                crate::GenerationError::Unsupported(path, _) => path.as_str(),
+                other => panic!("Expected an Unsupported error, got {:?}", other),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["Level", "Settings/properties/scale"]);
+    }
 }
@@ -0,0 +1,245 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_yaml::{Mapping, Value};
+
+use crate::{
+    deserializer::{Schema, SchemaDef, SchemaRef},
+    parser::{pascal_case, ParseContext},
+    GenerationError,
+};
+
+/// How the schemas are read, e.g. how `$ref`s to other documents are resolved
+#[derive(Debug, Clone)]
+pub struct ResolverConfig {
+    /// Fetch `$ref`s to `http(s)://` URLs, only has an effect with the `remote-refs` feature.
+    /// Every document is fetched once per run.
+    pub remote_refs: bool,
+    /// Fail with `GenerationError::UnknownRequiredProperty` when the `required` of an object
+    /// lists a name that isn't in its `properties`, instead of ignoring it
+    pub strict_required: bool,
+    /// Parse the schemas on the rayon thread pool, off they're parsed one after the other on the
+    /// current thread. The result is the same either way, only has an effect with the `parallel`
+    /// feature.
+    pub parallel: bool,
+}
+
+impl Default for ResolverConfig {
+    fn default() -> Self {
+        Self {
+            remote_refs: false,
+            strict_required: false,
+            parallel: true,
+        }
+    }
+}
+
+/// Resolves a JSON pointer (RFC 6901) like `/components/schemas` inside a yaml document
+pub(crate) fn resolve_pointer<'a>(document: &'a Value, pointer: &str) -> Option<&'a Value> {
+    pointer
+        .trim_start_matches('#')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .try_fold(document, |value, segment| match value {
+            Value::Sequence(items) => items.get(segment.parse::<usize>().ok()?),
+            value => value.get(segment.as_str()),
+        })
+}
+
+/// Collects the messages of an asyncapi 3 document reachable through
+/// `channels -> <channel> -> messages` and `operations -> <operation> -> messages` into a single
+/// mapping, keyed by the channel (or operation) name followed by the message name. Messages behind
+/// a `$ref` are followed, except for operations referencing a channel message as those are
+/// already collected through the channel.
+pub(crate) fn collect_channel_messages(document: &Value) -> Result<Value, GenerationError> {
+    let mut messages = Mapping::new();
+    for (channel, channel_def) in mapping_entries(&document["channels"]) {
+        for (message, message_def) in mapping_entries(&channel_def["messages"]) {
+            let message_def = follow_ref(document, message_def)?;
+            let name = format!("{}{}", pascal_case(channel), pascal_case(message));
+            messages.entry(name.into()).or_insert(message_def.clone());
+        }
+    }
+    for (operation, operation_def) in mapping_entries(&document["operations"]) {
+        let Some(operation_messages) = operation_def["messages"].as_sequence() else {
+            continue;
+        };
+        for (index, message_def) in operation_messages.iter().enumerate() {
+            let message = match message_def["$ref"].as_str() {
+                Some(pointer) if pointer.starts_with("#/channels/") => continue,
+                Some(pointer) => pointer.rsplit('/').next().unwrap_or_default().to_string(),
+                None => message_def["name"]
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("Message{}", index)),
+            };
+            let message_def = follow_ref(document, message_def)?;
+            let name = format!("{}{}", pascal_case(operation), pascal_case(&message));
+            messages.entry(name.into()).or_insert(message_def.clone());
+        }
+    }
+    Ok(Value::Mapping(messages))
+}
+
+/// Iterates over the string keyed entries of a mapping, anything else is treated as empty
+fn mapping_entries(value: &Value) -> impl Iterator<Item = (&str, &Value)> {
+    value
+        .as_mapping()
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| Some((key.as_str()?, value)))
+}
+
+/// Returns the target of `value` if it is a `$ref`, otherwise `value` itself
+fn follow_ref<'a>(document: &'a Value, value: &'a Value) -> Result<&'a Value, GenerationError> {
+    match value["$ref"].as_str() {
+        Some(pointer) => pointer
+            .strip_prefix('#')
+            .and_then(|pointer| resolve_pointer(document, pointer))
+            .ok_or_else(|| GenerationError::UnresolvedRef(pointer.to_string())),
+        None => Ok(value),
+    }
+}
+
+/// Follows every `$ref` reachable from the `sections` of schemas and makes sure it points
+/// somewhere in the `document`. Targets that can be generated as an entity of their own are added
+/// to `schemas` under the name derived from their pointer, every other target ends up in the
+/// returned `ParseContext` to be parsed in place of the ref.
+pub(crate) fn resolve_refs(
+    document: &Value,
+    sections: &[&Value],
+    components_pointer: &str,
+    schemas: &mut HashMap<String, SchemaDef>,
+    config: &ResolverConfig,
+) -> Result<ParseContext, GenerationError> {
+    let components_prefix = format!("#{}/", components_pointer.trim_end_matches('/'));
+    let mut ctx = ParseContext {
+        parallel: config.parallel,
+        ..Default::default()
+    };
+    let mut pending = vec![];
+    let mut visited = HashSet::new();
+    let mut remote_documents = HashMap::new();
+    for section in sections {
+        collect_refs(section, &mut pending);
+    }
+    while let Some(pointer) = pending.pop() {
+        // `#` refers to the enclosing top-level schema, the parser resolves it by itself
+        if pointer == "#" || !visited.insert(pointer.clone()) {
+            continue;
+        }
+        if let Some(name) = pointer.strip_prefix(&components_prefix) {
+            if schemas.contains_key(name) {
+                continue;
+            }
+        }
+        let target = match pointer.split_once('#') {
+            Some(("", fragment)) => resolve_pointer(document, fragment).cloned(),
+            Some((url, fragment)) if is_remote(url) && config.remote_refs => {
+                if !remote_documents.contains_key(url) {
+                    remote_documents.insert(url.to_string(), fetch_document(url)?);
+                }
+                resolve_pointer(&remote_documents[url], fragment)
+                    .cloned()
+                    .map(|mut target| {
+                        // refs inside the remote document are relative to it
+                        make_refs_absolute(&mut target, url);
+                        target
+                    })
+            }
+            _ => None,
+        }
+        .ok_or_else(|| GenerationError::UnresolvedRef(pointer.clone()))?;
+        collect_refs(&target, &mut pending);
+        match serde_yaml::from_value::<Schema>(target)? {
+            Schema::Def(
+                schema_def @ (SchemaDef::Object {
+                    properties: Some(_),
+                    ..
+                }
+                | SchemaDef::AllOf { .. }
+                | SchemaDef::OneOf { .. }
+                | SchemaDef::AnyOf { .. }
+                | SchemaDef::Enum { .. }),
+            ) => {
+                let name = SchemaRef {
+                    schema_path: pointer,
+                }
+                .get_schema_name();
+                // another schema by that name, e.g. `#/definitions/Foo` next to
+                // `#/components/schemas/Foo`, the ref would silently point to the wrong one
+                if schemas.contains_key(&name) {
+                    return Err(GenerationError::DuplicateSchema(name));
+                }
+                schemas.insert(name, schema_def);
+            }
+            schema => {
+                ctx.resolved_refs.insert(pointer, schema);
+            }
+        }
+    }
+    ctx.schema_names = schemas.keys().cloned().collect();
+    Ok(ctx)
+}
+
+fn is_remote(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+#[cfg(feature = "remote-refs")]
+fn fetch_document(url: &str) -> Result<Value, GenerationError> {
+    let error = |error: &dyn std::fmt::Display| {
+        GenerationError::RemoteRef(url.to_string(), error.to_string())
+    };
+    let body = ureq::get(url)
+        .call()
+        .map_err(|e| error(&e))?
+        .into_string()
+        .map_err(|e| error(&e))?;
+    let mut document = serde_yaml::from_str(&body)?;
+    crate::deserializer::normalize_tuple_items(&mut document);
+    Ok(document)
+}
+
+#[cfg(not(feature = "remote-refs"))]
+fn fetch_document(url: &str) -> Result<Value, GenerationError> {
+    Err(GenerationError::RemoteRef(
+        url.to_string(),
+        "the `remote-refs` feature is not enabled".to_string(),
+    ))
+}
+
+/// Prefixes the document local `$ref`s (`#/...`) in `value` with the `url` of their document
+fn make_refs_absolute(value: &mut Value, url: &str) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping.iter_mut() {
+                match (key.as_str(), value) {
+                    (Some("$ref"), Value::String(pointer)) if pointer.starts_with('#') => {
+                        pointer.insert_str(0, url);
+                    }
+                    (_, value) => make_refs_absolute(value, url),
+                }
+            }
+        }
+        Value::Sequence(items) => items
+            .iter_mut()
+            .for_each(|item| make_refs_absolute(item, url)),
+        _ => {}
+    }
+}
+
+fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping {
+                match (key.as_str(), value) {
+                    (Some("$ref"), Value::String(pointer)) => refs.push(pointer.clone()),
+                    _ => collect_refs(value, refs),
+                }
+            }
+        }
+        Value::Sequence(items) => items.iter().for_each(|item| collect_refs(item, refs)),
+        _ => {}
+    }
+}
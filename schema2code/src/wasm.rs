@@ -0,0 +1,14 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{generate_rust_with, GeneratorConfig};
+
+/// `generate_rust` for JavaScript, the error is the message of the `GenerationError`. Runs on the
+/// calling thread as there are no threads to spawn in the browser.
+#[wasm_bindgen(js_name = generateRust)]
+pub fn generate_rust_wasm(input: &str) -> Result<String, JsValue> {
+    let config = GeneratorConfig {
+        parallel: false,
+        ..Default::default()
+    };
+    generate_rust_with(input, &config).map_err(|e| JsValue::from_str(&e.to_string()))
+}
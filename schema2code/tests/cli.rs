@@ -0,0 +1,105 @@
+use std::process::Command;
+
+const BIN: &str = env!("CARGO_BIN_EXE_schema2code");
+
+#[test]
+fn test_generate_rust_file() {
+    let output = std::env::temp_dir().join(format!("schema2code-cli-{}.rs", std::process::id()));
+    let status = Command::new(BIN)
+        .args([
+            "--input",
+            "resources/asyncapi.yaml",
+            "--target",
+            "rust",
+            "--output",
+        ])
+        .arg(&output)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let code = std::fs::read_to_string(&output).unwrap();
+    std::fs::remove_file(&output).unwrap();
+    assert!(code.contains("pub struct RequestBase"));
+}
+
+#[test]
+fn test_generate_protobuf_stdout() {
+    let output = Command::new(BIN)
+        .args(["--input", "resources/asyncapi.yaml", "--target", "protobuf"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("message RequestBase"));
+}
+
+#[test]
+fn test_generate_typescript_stdout() {
+    let output = Command::new(BIN)
+        .args([
+            "--input",
+            "resources/asyncapi.yaml",
+            "--target",
+            "typescript",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("export interface RequestBase"));
+}
+
+#[test]
+fn test_missing_input_fails() {
+    let output = Command::new(BIN)
+        .args(["--input", "resources/does-not-exist.yaml"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Failed to read"));
+}
+
+#[test]
+fn test_unknown_pointer_fails() {
+    let output = Command::new(BIN)
+        .args([
+            "--input",
+            "resources/asyncapi.yaml",
+            "--components-pointer",
+            "/definitions",
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_invalid_display_template_fails() {
+    let input = std::env::temp_dir().join(format!("schema2code-cli-{}.yaml", std::process::id()));
+    std::fs::write(
+        &input,
+        r#"
+components:
+  schemas:
+    Point:
+      type: object
+      x-display: '{x}, {z}'
+      properties:
+        x:
+          type: integer
+"#,
+    )
+    .unwrap();
+    let output = Command::new(BIN)
+        .arg("--input")
+        .arg(&input)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&input).unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("`z`"));
+}
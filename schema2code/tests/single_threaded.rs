@@ -0,0 +1,30 @@
+//! Kept in a binary of its own, other tests in the same process would spawn threads meanwhile
+
+use schema2code::{generate_rust_with, GeneratorConfig};
+
+#[cfg(target_os = "linux")]
+fn thread_count() -> usize {
+    std::fs::read_dir("/proc/self/task").unwrap().count()
+}
+
+/// With `parallel` off generating has to work where there are no threads or processes to spawn,
+/// e.g. in wasm
+#[test]
+#[cfg(target_os = "linux")]
+fn test_sequential_generation_spawns_no_threads() {
+    let input = include_str!("../resources/asyncapi.yaml");
+    let sequential = GeneratorConfig {
+        parallel: false,
+        ..Default::default()
+    };
+    let before = thread_count();
+    let code = generate_rust_with(input, &sequential).unwrap();
+    assert!(code.contains("pub struct RequestBase"));
+    assert_eq!(thread_count(), before);
+    // the rayon pool outlives the generation, so the check above would notice it
+    #[cfg(feature = "parallel")]
+    {
+        generate_rust_with(input, &GeneratorConfig::default()).unwrap();
+        assert!(thread_count() > before);
+    }
+}